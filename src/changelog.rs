@@ -0,0 +1,349 @@
+//! Conventional commit parsing and Keep a Changelog sections
+
+use crate::git::Commit;
+use regex::Regex;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `type(scope)!: description`, optionally after a gitmoji
+fn conventional_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(?:[^\x00-\x7F]+\s*)?([A-Za-z]+)(?:\(([^)]*)\))?(!)?:\s*(.+)$").unwrap()
+    })
+}
+
+/// Header written to a new CHANGELOG.md
+const HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+";
+
+/// A commit subject parsed as a conventional commit. Subjects that don't
+/// follow the convention get an empty type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conventional {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl Conventional {
+    pub fn parse(commit: &Commit) -> Self {
+        let breaking_footer = commit.body.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+
+        match conventional_pattern().captures(commit.subject.trim()) {
+            Some(captures) => Self {
+                kind: captures[1].to_ascii_lowercase(),
+                scope: captures.get(2).map(|m| m.as_str().to_string()),
+                breaking: captures.get(3).is_some() || breaking_footer,
+                description: captures[4].trim().to_string(),
+            },
+            None => Self {
+                kind: String::new(),
+                scope: None,
+                breaking: breaking_footer,
+                description: commit.subject.trim().to_string(),
+            },
+        }
+    }
+}
+
+/// Keep a Changelog section, in the order they appear in a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl Section {
+    /// Section for a conventional commit type. Types that don't affect users
+    /// (docs, tests, CI, ...) have none unless `include_internal` is set.
+    pub fn for_kind(kind: &str, include_internal: bool) -> Option<Self> {
+        match kind {
+            "feat" => Some(Self::Added),
+            "fix" => Some(Self::Fixed),
+            "remove" => Some(Self::Removed),
+            "deprecate" => Some(Self::Deprecated),
+            "security" => Some(Self::Security),
+            // Reverting a change is a change of its own, not a removal
+            "perf" | "refactor" | "revert" | "" => Some(Self::Changed),
+            "docs" | "style" | "test" | "tests" | "chore" | "ci" | "build" => {
+                include_internal.then_some(Self::Changed)
+            }
+            _ => Some(Self::Changed),
+        }
+    }
+}
+
+impl std::fmt::Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => write!(f, "Added"),
+            Self::Changed => write!(f, "Changed"),
+            Self::Deprecated => write!(f, "Deprecated"),
+            Self::Removed => write!(f, "Removed"),
+            Self::Fixed => write!(f, "Fixed"),
+            Self::Security => write!(f, "Security"),
+        }
+    }
+}
+
+/// A commit sorted into a changelog section
+#[derive(Debug, Clone)]
+pub struct Entry<'a> {
+    pub commit: &'a Commit,
+    pub conventional: Conventional,
+}
+
+impl Entry<'_> {
+    fn line(&self) -> String {
+        let scope = match self.conventional.scope {
+            Some(ref scope) => format!("**{}:** ", scope),
+            None => String::new(),
+        };
+        let breaking = if self.conventional.breaking {
+            "**BREAKING:** "
+        } else {
+            ""
+        };
+        format!(
+            "- {}{}{} ({})",
+            breaking,
+            scope,
+            self.conventional.description,
+            self.commit.short_hash()
+        )
+    }
+}
+
+/// Sort commits into sections, oldest first within a section. Breaking
+/// changes are always included.
+pub fn group(commits: &[Commit], include_internal: bool) -> Vec<(Section, Vec<Entry<'_>>)> {
+    let mut groups: Vec<(Section, Vec<Entry>)> = Vec::new();
+    for commit in commits.iter().rev() {
+        let conventional = Conventional::parse(commit);
+        let section = Section::for_kind(&conventional.kind, include_internal)
+            .or_else(|| conventional.breaking.then_some(Section::Changed));
+        let Some(section) = section else {
+            continue;
+        };
+        let entry = Entry {
+            commit,
+            conventional,
+        };
+        match groups.iter_mut().find(|(s, _)| *s == section) {
+            Some((_, entries)) => entries.push(entry),
+            None => groups.push((section, vec![entry])),
+        }
+    }
+    groups.sort_by_key(|(section, _)| *section);
+    groups
+}
+
+/// Render grouped entries as `### Section` lists
+pub fn render_groups(groups: &[(Section, Vec<Entry>)]) -> String {
+    groups
+        .iter()
+        .map(|(section, entries)| {
+            let lines: Vec<String> = entries.iter().map(Entry::line).collect();
+            format!("### {}\n\n{}\n", section, lines.join("\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Heading of a release section: `## [Unreleased]` or `## [1.2.0] - 2024-05-01`
+pub fn heading(version: &str, date: &str) -> String {
+    if version.eq_ignore_ascii_case("unreleased") {
+        "## [Unreleased]".to_string()
+    } else {
+        format!("## [{}] - {}", version.trim_start_matches('v'), date)
+    }
+}
+
+/// Insert a release section into a changelog, above the previous releases.
+/// A section with the same heading version is replaced.
+pub fn insert_section(changelog: &str, section: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("{}\n{}\n", HEADER, section.trim_end());
+    }
+
+    let lines: Vec<&str> = changelog.lines().collect();
+    let version = section_version(section.lines().next().unwrap_or_default());
+    let is_release = |line: &&str| line.starts_with("## ");
+
+    let (start, end) = match lines
+        .iter()
+        .position(|line| is_release(line) && section_version(line) == version)
+    {
+        Some(start) => {
+            // The replaced section ends at the next release, or at the link
+            // references at the bottom of the file
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| is_release(line) || is_link_reference(line))
+                .map_or(lines.len(), |i| start + 1 + i);
+            (start, end)
+        }
+        None => {
+            let start = lines.iter().position(is_release).unwrap_or(lines.len());
+            (start, start)
+        }
+    };
+
+    let mut result = lines[..start].join("\n").trim_end().to_string();
+    if !result.is_empty() {
+        result.push_str("\n\n");
+    }
+    result.push_str(section.trim_end());
+    result.push('\n');
+    let rest = lines[end..].join("\n");
+    if !rest.trim().is_empty() {
+        result.push('\n');
+        result.push_str(rest.trim_start_matches('\n'));
+        result.push('\n');
+    }
+    result
+}
+
+/// `[1.0.0]: https://...` lines that Keep a Changelog puts at the end
+fn is_link_reference(line: &&str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+/// Version in a `## [1.2.0] - date` heading, lowercased
+fn section_version(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("## ")?.trim_start();
+    let version = match rest.strip_prefix('[') {
+        Some(rest) => rest.split(']').next()?,
+        None => rest.split_whitespace().next()?,
+    };
+    Some(version.trim_start_matches('v').to_ascii_lowercase())
+}
+
+/// Today's date (UTC) as YYYY-MM-DD
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_conventional_subjects() {
+        let parsed = Conventional::parse(&Commit::test("feat(api)!: drop v1 routes"));
+        assert_eq!(parsed.kind, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert!(parsed.breaking);
+        assert_eq!(parsed.description, "drop v1 routes");
+
+        let gitmoji = Conventional::parse(&Commit::test("🐛 fix: handle empty input"));
+        assert_eq!(gitmoji.kind, "fix");
+
+        let footer = Conventional::parse(
+            &Commit::test("refactor: config").with_body("BREAKING CHANGE: renamed keys"),
+        );
+        assert!(footer.breaking);
+
+        let free = Conventional::parse(&Commit::test("Update README"));
+        assert_eq!(free.kind, "");
+        assert_eq!(free.description, "Update README");
+    }
+
+    #[test]
+    fn section_for_kinds() {
+        assert_eq!(Section::for_kind("revert", false), Some(Section::Changed));
+        assert_eq!(Section::for_kind("remove", false), Some(Section::Removed));
+        assert_eq!(Section::for_kind("docs", false), None);
+        assert_eq!(Section::for_kind("docs", true), Some(Section::Changed));
+    }
+
+    #[test]
+    fn group_by_section_skips_internal_changes() {
+        // git log order: newest first
+        let commits = vec![
+            Commit::test("chore!: require Rust 1.80"),
+            Commit::test("docs: typo"),
+            Commit::test("fix: crash on empty diff"),
+            Commit::test("feat: add review"),
+            Commit::test("feat: add changelog"),
+        ];
+        let groups = group(&commits, false);
+        let sections: Vec<Section> = groups.iter().map(|(s, _)| *s).collect();
+        assert_eq!(
+            sections,
+            vec![Section::Added, Section::Changed, Section::Fixed]
+        );
+        assert_eq!(groups[0].1[0].conventional.description, "add changelog");
+
+        let rendered = render_groups(&groups);
+        assert!(rendered.starts_with("### Added\n\n- add changelog ("));
+        assert!(rendered.contains("### Changed\n\n- **BREAKING:** require Rust 1.80"));
+        assert!(!rendered.contains("typo"));
+
+        assert_eq!(group(&commits, true)[1].1.len(), 2);
+    }
+
+    #[test]
+    fn insert_section_above_previous_releases() {
+        let existing = "# Changelog\n\nIntro.\n\n## [1.0.0] - 2024-01-01\n\n### Added\n\n- First\n\n[1.0.0]: https://example.com\n";
+        let section = "## [1.1.0] - 2024-05-01\n\n### Fixed\n\n- Bug\n";
+        let updated = insert_section(existing, section);
+        assert_eq!(
+            updated,
+            "# Changelog\n\nIntro.\n\n## [1.1.0] - 2024-05-01\n\n### Fixed\n\n- Bug\n\n## [1.0.0] - 2024-01-01\n\n### Added\n\n- First\n\n[1.0.0]: https://example.com\n"
+        );
+    }
+
+    #[test]
+    fn insert_section_replaces_same_version() {
+        let existing =
+            "# Changelog\n\n## [Unreleased]\n\n- Old\n\n## [1.0.0] - 2024-01-01\n\n- First\n";
+        let updated = insert_section(existing, "## [Unreleased]\n\n- New\n");
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased]\n\n- New\n\n## [1.0.0] - 2024-01-01\n\n- First\n"
+        );
+        assert!(insert_section("", "## [Unreleased]\n\n- New\n").starts_with("# Changelog\n"));
+    }
+
+    #[test]
+    fn heading_and_dates() {
+        assert_eq!(heading("v1.2.0", "2024-05-01"), "## [1.2.0] - 2024-05-01");
+        assert_eq!(heading("unreleased", "2024-05-01"), "## [Unreleased]");
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_844), (2024, 5, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+}
@@ -0,0 +1,185 @@
+//! CODEOWNERS parsing and matching
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Locations searched for a CODEOWNERS file, in order
+const LOCATIONS: [&str; 4] = [
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// A parsed CODEOWNERS file
+#[derive(Debug)]
+pub struct CodeOwners {
+    pub path: PathBuf,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    pattern: Regex,
+    owners: Vec<String>,
+}
+
+/// Owners of a set of changed files
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Ownership {
+    /// Owners in order of first appearance
+    pub owners: Vec<String>,
+    /// Changed paths no rule assigns an owner to
+    pub unowned: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Load the first CODEOWNERS file found under `root`
+    pub fn find(root: &Path) -> Option<Self> {
+        LOCATIONS.iter().find_map(|location| {
+            let path = root.join(location);
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some(Self::parse(PathBuf::from(location), &content))
+        })
+    }
+
+    pub fn parse(path: PathBuf, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            // Skip comments and GitLab section headers like [Docs]
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('['))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = pattern_to_regex(parts.next()?)?;
+                let owners = parts
+                    .take_while(|part| !part.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                Some(Rule { pattern, owners })
+            })
+            .collect();
+        Self { path, rules }
+    }
+
+    /// Owners of `path`; the last matching rule wins
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.is_match(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Collect the owners of all `files` and the files without an owner
+    pub fn ownership(&self, files: &[String]) -> Ownership {
+        let mut ownership = Ownership::default();
+        for file in files {
+            let owners = self.owners_of(file);
+            if owners.is_empty() {
+                ownership.unowned.push(file.clone());
+            }
+            for owner in owners {
+                if !ownership.owners.contains(owner) {
+                    ownership.owners.push(owner.clone());
+                }
+            }
+        }
+        ownership
+    }
+}
+
+/// Translate a gitignore-style CODEOWNERS pattern into a regex over repository paths
+pub fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let trimmed = pattern.trim_end_matches('/');
+    // A slash at the start or in the middle anchors the pattern to the root
+    let anchored = trimmed.contains('/');
+    let glob = trimmed.trim_start_matches('/');
+    if glob.is_empty() {
+        return None;
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    // `dir/*` only matches direct children; anything else also matches
+    // everything below a matching directory
+    if glob.ends_with("/*") {
+        regex.push('$');
+    } else {
+        regex.push_str("(?:/.*)?$");
+    }
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(content: &str, path: &str) -> Vec<String> {
+        CodeOwners::parse(PathBuf::from("CODEOWNERS"), content)
+            .owners_of(path)
+            .to_vec()
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let content = "* @org/everyone\n*.rs @rustacean\n/docs/ @writer # docs team\n";
+        assert_eq!(owners(content, "README.md"), vec!["@org/everyone"]);
+        assert_eq!(owners(content, "src/main.rs"), vec!["@rustacean"]);
+        assert_eq!(owners(content, "docs/guide/intro.md"), vec!["@writer"]);
+    }
+
+    #[test]
+    fn unanchored_and_anchored_patterns() {
+        let content = "apps/ @apps\n/build/logs/ @ops\ndocs/* @docs\n**/migrations @db\n";
+        assert_eq!(owners(content, "web/apps/index.js"), vec!["@apps"]);
+        assert_eq!(owners(content, "build/logs/today.log"), vec!["@ops"]);
+        assert!(owners(content, "src/build/logs/today.log").is_empty());
+        assert_eq!(owners(content, "docs/intro.md"), vec!["@docs"]);
+        assert!(owners(content, "docs/guide/intro.md").is_empty());
+        assert_eq!(owners(content, "db/migrations/001.sql"), vec!["@db"]);
+    }
+
+    #[test]
+    fn ownership_reports_unowned_paths() {
+        let codeowners = CodeOwners::parse(
+            PathBuf::from("CODEOWNERS"),
+            "[Backend]\n/src/ @alice @bob\n/src/generated/\n",
+        );
+        let files = vec![
+            "src/lib.rs".to_string(),
+            "src/generated/api.rs".to_string(),
+            "README.md".to_string(),
+        ];
+        assert_eq!(
+            codeowners.ownership(&files),
+            Ownership {
+                owners: vec!["@alice".to_string(), "@bob".to_string()],
+                unowned: vec!["src/generated/api.rs".to_string(), "README.md".to_string()],
+            }
+        );
+    }
+}
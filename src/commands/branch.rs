@@ -0,0 +1,288 @@
+use super::commit::{parse_suggestions, truncate_diff};
+use super::GenerationArgs;
+use crate::config::{BranchConfig, Config, Language};
+use crate::git::{Git, GitError};
+use crate::issues;
+use crate::llm::LlmClient;
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Select};
+use std::io::IsTerminal;
+
+/// Longest slug kept from a suggestion, in bytes
+const MAX_SLUG_LEN: usize = 40;
+
+#[derive(Args)]
+pub struct BranchArgs {
+    /// What the branch is for, e.g. "add OAuth login for admins"
+    #[arg(required_unless_present = "staged")]
+    pub description: Option<String>,
+
+    /// Describe the staged changes instead of (or in addition to) a description
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Ticket key for the {ticket} placeholder (default: found in the description)
+    #[arg(long)]
+    pub ticket: Option<String>,
+
+    /// Create the branch with the first suggestion
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the suggested names, don't create a branch
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Number of suggestions to generate (overrides config)
+    #[arg(short = 'n', long)]
+    pub count: Option<usize>,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+pub async fn run(args: BranchArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+    if let Some(count) = args.count {
+        config.options.suggestions = count;
+    }
+
+    let diff = if args.staged {
+        match Git::get_staged_diff() {
+            Ok(diff) => Some(diff),
+            Err(GitError::NoStagedChanges) => {
+                eprintln!("{} No staged changes", "Error:".red().bold());
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let ticket = args.ticket.clone().or_else(|| {
+        let patterns = issues::patterns(&config.issues).ok()?;
+        let description = args.description.clone()?;
+        issues::extract(&[description], &patterns)
+            .into_iter()
+            .next()
+    });
+
+    println!("{}", "Generating branch names...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let prompt = build_branch_prompt(args.description.as_deref(), diff.as_deref(), &config);
+    let completion = client.generate(&prompt).await?;
+    let mut names: Vec<String> = Vec::new();
+    for suggestion in parse_suggestions(&completion.text) {
+        if let Some(name) = branch_name(&suggestion, ticket.as_deref(), &config.branch) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names.truncate(config.options.suggestions.max(1));
+
+    if names.is_empty() {
+        eprintln!("{}", "Failed to generate a branch name.".red());
+        std::process::exit(1);
+    }
+
+    if args.dry_run {
+        println!("\n{}", "Suggested branch names:".green().bold());
+        for (i, name) in names.iter().enumerate() {
+            println!("{}. {}", i + 1, name);
+        }
+        return Ok(());
+    }
+
+    let selected = if args.yes || names.len() == 1 || !std::io::stdin().is_terminal() {
+        names[0].clone()
+    } else {
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select a branch name")
+            .items(&names)
+            .default(0)
+            .interact()?;
+        names[selection].clone()
+    };
+
+    if Git::has_branch(&selected) {
+        eprintln!(
+            "{} Branch '{}' already exists",
+            "Error:".red().bold(),
+            selected
+        );
+        std::process::exit(1);
+    }
+    Git::switch_create(&selected)?;
+    println!(
+        "\n{} {}",
+        "✓".green().bold(),
+        "Switched to a new branch:".green()
+    );
+    println!("  {}", selected);
+
+    Ok(())
+}
+
+fn build_branch_prompt(description: Option<&str>, diff: Option<&str>, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Even if the description is in Korean, write the descriptions in English.",
+        Language::En => "Write the descriptions in English.",
+    };
+
+    let count = config.options.suggestions.max(1);
+    let types = config.branch.types.join(", ");
+
+    let mut context = String::new();
+    if let Some(description) = description {
+        context.push_str(&format!("Description of the work:\n{}\n", description));
+    }
+    if let Some(diff) = diff {
+        context.push_str(&format!(
+            "\nStaged changes (truncated if too long):\n```diff\n{}\n```\n",
+            truncate_diff(diff, 4000)
+        ));
+    }
+
+    format!(
+        r#"Suggest {count} names for a new Git branch for the work below.
+
+Instructions:
+- {language_instruction}
+- Output a numbered list. Each item is a branch type, a colon, and a short description of 2 to 5 words.
+- The branch type must be one of: {types}
+- Don't include ticket or issue numbers; they are added separately.
+- Example: 1. feature: oauth login for admins
+
+{context}"#,
+        count = count,
+        language_instruction = language_instruction,
+        types = types,
+        context = context.trim_end()
+    )
+}
+
+/// Build a branch name from a `type: description` suggestion
+fn branch_name(suggestion: &str, ticket: Option<&str>, config: &BranchConfig) -> Option<String> {
+    let (kind, description) = match suggestion.split_once(':') {
+        Some((kind, description)) => (slugify(kind), description),
+        None => (String::new(), suggestion),
+    };
+    let kind = if config.types.contains(&kind) {
+        kind
+    } else {
+        config.types.first().cloned().unwrap_or_default()
+    };
+
+    let slug = slugify(description);
+    if slug.is_empty() {
+        return None;
+    }
+    let ticket = ticket.map(|t| t.trim_start_matches('#'));
+    Some(render_pattern(&config.pattern, &kind, ticket, &slug))
+}
+
+/// Fill in the pattern's placeholders. Without a ticket, `{ticket}` is
+/// removed with the separator after it (or before it, at the end).
+fn render_pattern(pattern: &str, kind: &str, ticket: Option<&str>, slug: &str) -> String {
+    let pattern = match ticket {
+        Some(ticket) => pattern.replace("{ticket}", ticket),
+        None => {
+            let mut pattern = pattern.to_string();
+            while let Some(start) = pattern.find("{ticket}") {
+                let mut end = start + "{ticket}".len();
+                let mut start = start;
+                if pattern[end..].starts_with(['-', '_', '/']) {
+                    end += 1;
+                } else if pattern[..start].ends_with(['-', '_', '/']) {
+                    start -= 1;
+                }
+                pattern.replace_range(start..end, "");
+            }
+            pattern
+        }
+    };
+    pattern.replace("{type}", kind).replace("{slug}", slug)
+}
+
+/// Lowercase words joined by hyphens, cut at a word boundary
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for word in text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + 1 + word.len() > MAX_SLUG_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_joins_words() {
+        assert_eq!(
+            slugify(" OAuth login for admins!"),
+            "oauth-login-for-admins"
+        );
+        assert_eq!(slugify("`fix` crash_on start"), "fix-crash-on-start");
+        assert!(slugify(&"word ".repeat(20)).len() <= MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn render_pattern_with_and_without_ticket() {
+        let pattern = "{type}/{ticket}-{slug}";
+        assert_eq!(
+            render_pattern(pattern, "feature", Some("PROJ-12"), "oauth-login"),
+            "feature/PROJ-12-oauth-login"
+        );
+        assert_eq!(
+            render_pattern(pattern, "feature", None, "oauth-login"),
+            "feature/oauth-login"
+        );
+        assert_eq!(
+            render_pattern("{slug}_{ticket}", "fix", None, "crash"),
+            "crash"
+        );
+    }
+
+    #[test]
+    fn branch_name_falls_back_to_first_type() {
+        let config = BranchConfig::default();
+        assert_eq!(
+            branch_name("fix: Crash on start", Some("#42"), &config).as_deref(),
+            Some("fix/42-crash-on-start")
+        );
+        assert_eq!(
+            branch_name("feat: oauth login", None, &config).as_deref(),
+            Some("feature/oauth-login")
+        );
+        assert_eq!(branch_name("docs:", None, &config), None);
+    }
+}
@@ -0,0 +1,247 @@
+use super::pr::strip_code_fence;
+use super::GenerationArgs;
+use crate::changelog::{self, Section};
+use crate::config::{Config, Language};
+use crate::git::Git;
+use crate::llm::{LlmClient, Usage};
+use crate::output::{print_json, OutputFormat};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Args)]
+pub struct ChangelogArgs {
+    /// Start of the range (default: the last tag)
+    #[arg(long, value_name = "REF")]
+    pub from: Option<String>,
+
+    /// End of the range
+    #[arg(long, value_name = "REF", default_value = "HEAD")]
+    pub to: String,
+
+    /// Version for the section heading
+    #[arg(long, value_name = "VERSION", default_value = "Unreleased")]
+    pub release: String,
+
+    /// Include docs, tests, CI and chores
+    #[arg(long)]
+    pub all: bool,
+
+    /// Insert the section into a changelog file (default: CHANGELOG.md at the repository root)
+    #[arg(long, short = 'w', value_name = "PATH", num_args = 0..=1)]
+    pub write: Option<Option<PathBuf>>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// JSON document printed by `changelog --output json`
+#[derive(Serialize)]
+struct ChangelogOutput<'a> {
+    version: &'a str,
+    from: Option<&'a str>,
+    to: &'a str,
+    commits: usize,
+    markdown: &'a str,
+    path: Option<PathBuf>,
+    provider: &'a str,
+    model: &'a str,
+    usage: Option<Usage>,
+    duration_ms: u128,
+}
+
+pub async fn run(args: ChangelogArgs) -> anyhow::Result<()> {
+    let output = args.output;
+
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let from = args.from.clone().or_else(|| previous_tag(&args.to));
+    let commits = match Git::get_commit_log_without_merges(from.as_deref(), &args.to) {
+        Ok(commits) => commits,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    output.status(format!(
+        "{} {} commit(s) in {}..{}",
+        "Collecting:".dimmed(),
+        commits.len(),
+        from.as_deref().unwrap_or("(start)").cyan(),
+        args.to.green()
+    ));
+
+    let groups = changelog::group(&commits, args.all);
+    if groups.is_empty() {
+        eprintln!("{}", "No user-facing changes found.".yellow());
+        std::process::exit(1);
+    }
+
+    output.status("Writing changelog entries...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let started = Instant::now();
+    let completion = client
+        .generate(&build_changelog_prompt(
+            &changelog::render_groups(&groups),
+            &commits_context(&groups),
+            &config,
+        ))
+        .await?;
+    let duration = started.elapsed();
+
+    let markdown = format!(
+        "{}\n\n{}\n",
+        changelog::heading(&args.release, &changelog::today()),
+        clean_sections(&completion.text)
+    );
+
+    let path = match args.write {
+        Some(ref path) => {
+            let path = match path {
+                Some(path) => path.clone(),
+                None => Git::repo_root()?.join("CHANGELOG.md"),
+            };
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+            std::fs::write(&path, changelog::insert_section(&existing, &markdown))?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    if output.is_json() {
+        print_json(&ChangelogOutput {
+            version: &args.release,
+            from: from.as_deref(),
+            to: &args.to,
+            commits: commits.len(),
+            markdown: &markdown,
+            path,
+            provider: client.provider_name(),
+            model: client.model(),
+            usage: completion.usage,
+            duration_ms: duration.as_millis(),
+        })?;
+    } else {
+        println!("\n{}", markdown.trim_end());
+        if let Some(path) = path {
+            println!(
+                "\n{} {} {}",
+                "✓".green().bold(),
+                "Updated".green(),
+                path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Commit bodies give the model context the subjects lack
+pub fn commits_context(groups: &[(Section, Vec<changelog::Entry>)]) -> String {
+    groups
+        .iter()
+        .flat_map(|(_, entries)| entries)
+        .filter(|entry| !entry.commit.body.is_empty())
+        .map(|entry| {
+            format!(
+                "{} {}\n{}",
+                entry.commit.short_hash(),
+                entry.commit.subject,
+                entry.commit.body
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The last tag before `to`. A tag on `to` itself marks the release being
+/// written, so the range starts at the tag before it.
+fn previous_tag(to: &str) -> Option<String> {
+    let tag = Git::last_tag(to)?;
+    if Git::rev_parse(&tag).ok() == Git::rev_parse(to).ok() {
+        Git::last_tag(&format!("{}^", to))
+    } else {
+        Some(tag)
+    }
+}
+
+fn build_changelog_prompt(draft: &str, context: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the entries in Korean.",
+        Language::En => "Write the entries in English.",
+    };
+
+    let context = if context.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n\nCommit messages with details (truncated if too long):\n```\n{}\n```",
+            super::commit::truncate_diff(context, 6000)
+        )
+    };
+
+    format!(
+        r#"You are writing release notes for the users of a project, in the Keep a Changelog format.
+Below is a draft with commits grouped into sections.
+
+Instructions:
+- {language_instruction}
+- Rewrite each entry so that a user understands what changed for them, in plain language, starting with a verb or noun (e.g. "Support for ...", "Crash when ...").
+- Keep the section headings (### Added, ### Fixed, ...) and their order. Drop a section that ends up empty.
+- Merge entries that describe the same change and drop entries with no effect on users.
+- Keep "**BREAKING:**" markers and the commit hashes in parentheses.
+- Respond with ONLY the sections as markdown, without a release heading or any other text.
+
+Draft:
+```
+{draft}
+```{context}"#,
+        language_instruction = language_instruction,
+        draft = draft,
+        context = context
+    )
+}
+
+/// Keep only the `###` sections of the response, dropping any heading or
+/// remarks the model added around them
+pub fn clean_sections(response: &str) -> String {
+    let text = strip_code_fence(response.trim());
+    match text.find("### ") {
+        Some(start) => text[start..].trim().to_string(),
+        None => text.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_sections_drops_surrounding_text() {
+        let response = "```markdown\n## [1.0.0]\n\n### Added\n\n- Reviews (abc1234)\n```";
+        assert_eq!(clean_sections(response), "### Added\n\n- Reviews (abc1234)");
+        assert_eq!(clean_sections("- Only entries"), "- Only entries");
+    }
+}
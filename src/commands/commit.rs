@@ -1,10 +1,15 @@
-use crate::config::{AutoStage, Config, Format, Language};
+use super::GenerationArgs;
+use crate::config::{AutoStage, Config, Format, IssueStyle, Language};
 use crate::git::Git;
-use crate::llm::LlmClient;
+use crate::issues;
+use crate::llm::{LlmClient, LlmError, Usage};
+use crate::output::{print_json, OutputFormat};
 use clap::Args;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+use serde::Serialize;
 use std::io::IsTerminal;
+use std::time::Instant;
 
 #[derive(Args)]
 pub struct CommitArgs {
@@ -23,21 +28,160 @@ pub struct CommitArgs {
     /// Specify the commit type for conventional commits
     #[arg(long)]
     pub r#type: Option<String>,
+
+    /// Number of suggestions to generate (overrides config)
+    #[arg(short = 'n', long)]
+    pub count: Option<usize>,
+
+    /// Regenerate the message of the last commit (includes newly staged changes)
+    #[arg(long)]
+    pub amend: bool,
+
+    /// Amend even if HEAD has already been pushed
+    #[arg(long, requires = "amend")]
+    pub force: bool,
+
+    /// Output format (json implies --dry-run)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// JSON document printed by `commit --output json`
+#[derive(Serialize)]
+struct CommitOutput<'a> {
+    suggestions: &'a [String],
+    provider: &'a str,
+    model: &'a str,
+    usage: Option<Usage>,
+    duration_ms: u128,
 }
 
 pub async fn run(args: CommitArgs) -> anyhow::Result<()> {
     // Load config early to check auto_stage setting
-    let config = Config::load()?;
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+    if let Some(count) = args.count {
+        config.options.suggestions = count;
+    }
+
+    let output = args.output;
 
     // Stage all changes if requested
     if args.all {
         Git::stage_all()?;
-        println!("{}", "Staged all changes.".dimmed());
+        output.status("Staged all changes.".dimmed());
+    }
+
+    // Get the diff to describe
+    let (diff, previous_message) = if args.amend {
+        amend_diff(args.force)?
+    } else {
+        (staged_diff(&config, output)?, None)
+    };
+
+    // Get LLM client
+    output.status("Generating commit message...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    // Generate commit message
+    let started = Instant::now();
+    let (messages, usage) = generate_messages(
+        &client,
+        &diff,
+        &config,
+        args.r#type.as_deref(),
+        previous_message.as_deref(),
+    )
+    .await?;
+
+    if messages.is_empty() {
+        eprintln!("{}", "Failed to generate commit message.".red());
+        std::process::exit(1);
+    }
+    let messages = link_issues(messages, &config);
+
+    if output.is_json() {
+        return print_json(&CommitOutput {
+            suggestions: &messages,
+            provider: client.provider_name(),
+            model: client.model(),
+            usage,
+            duration_ms: started.elapsed().as_millis(),
+        });
     }
 
-    // Get staged diff
-    let diff = match Git::get_staged_diff() {
-        Ok(d) => d,
+    // If dry-run, just print and exit
+    if args.dry_run {
+        println!("\n{}", "Generated commit message(s):".green().bold());
+        for (i, msg) in messages.iter().enumerate() {
+            println!("\n{}. {}", i + 1, msg);
+        }
+        return Ok(());
+    }
+
+    // Select message
+    let selected = if args.yes || messages.len() == 1 || !std::io::stdin().is_terminal() {
+        messages[0].clone()
+    } else {
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select a commit message")
+            .items(&messages)
+            .default(0)
+            .interact()?;
+        messages[selection].clone()
+    };
+
+    // Commit
+    if args.amend {
+        Git::commit_amend(&selected)?;
+        println!("\n{} {}", "✓".green().bold(), "Amended:".green());
+    } else {
+        Git::commit(&selected)?;
+        println!("\n{} {}", "✓".green().bold(), "Committed:".green());
+    }
+    println!("  {}", selected);
+
+    Ok(())
+}
+
+/// Reference the issue keys found in the branch name
+pub fn link_issues(messages: Vec<String>, config: &Config) -> Vec<String> {
+    if config.issues.style == IssueStyle::Off {
+        return messages;
+    }
+    match issues::detect(&config.issues, None) {
+        Ok(keys) => messages
+            .iter()
+            .map(|message| issues::apply_to_commit(message, &keys.branch, config.issues.style))
+            .collect(),
+        Err(e) => {
+            eprintln!(
+                "{} Invalid issue pattern: {}",
+                "Warning:".yellow().bold(),
+                e
+            );
+            messages
+        }
+    }
+}
+
+/// Get the staged diff, offering to stage changes when nothing is staged
+fn staged_diff(config: &Config, output: OutputFormat) -> anyhow::Result<String> {
+    match Git::get_staged_diff() {
+        Ok(d) => Ok(d),
         Err(crate::git::GitError::NoStagedChanges) => {
             // Check for unstaged or untracked changes
             let has_unstaged = Git::has_unstaged_changes().unwrap_or(false);
@@ -48,7 +192,7 @@ pub async fn run(args: CommitArgs) -> anyhow::Result<()> {
                 let should_stage = match config.options.auto_stage {
                     AutoStage::Always => true,
                     AutoStage::Never => {
-                        eprintln!("{} {}", "Error:".red().bold(), "No staged changes");
+                        eprintln!("{} No staged changes", "Error:".red().bold());
                         eprintln!();
                         eprintln!("{}", "You have unstaged changes. Try:".yellow());
                         eprintln!("  {} stage specific files first", "git add <files>".cyan());
@@ -76,15 +220,15 @@ pub async fn run(args: CommitArgs) -> anyhow::Result<()> {
 
                 if should_stage {
                     Git::stage_all()?;
-                    println!("{}", "Staged all changes.".dimmed());
+                    output.status("Staged all changes.".dimmed());
                     // Retry getting staged diff
-                    Git::get_staged_diff()?
+                    Ok(Git::get_staged_diff()?)
                 } else {
                     eprintln!("{}", "Aborted.".yellow());
                     std::process::exit(1);
                 }
             } else {
-                eprintln!("{} {}", "Error:".red().bold(), "No changes to commit");
+                eprintln!("{} No changes to commit", "Error:".red().bold());
                 std::process::exit(1);
             }
         }
@@ -92,70 +236,51 @@ pub async fn run(args: CommitArgs) -> anyhow::Result<()> {
             eprintln!("{} {}", "Error:".red().bold(), e);
             std::process::exit(1);
         }
-    };
-
-    // Build prompt
-    let prompt = build_commit_prompt(&diff, &config, args.r#type.as_deref());
-
-    // Get LLM client
-    println!("{}", "Generating commit message...".dimmed());
-    let client = match LlmClient::from_config(&config) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("{} {}", "Error:".red().bold(), e);
-            eprintln!(
-                "{}",
-                "Run 'git ai config' to configure your LLM provider.".yellow()
-            );
-            std::process::exit(1);
-        }
-    };
-
-    // Generate commit message
-    let response = client.generate(&prompt).await?;
-    let messages = parse_suggestions(&response);
+    }
+}
 
-    if messages.is_empty() {
-        eprintln!("{}", "Failed to generate commit message.".red());
+/// Get the diff of HEAD against its parent plus staged changes, with HEAD's message
+fn amend_diff(force: bool) -> anyhow::Result<(String, Option<String>)> {
+    if !Git::has_head() {
+        eprintln!("{} No commit to amend", "Error:".red().bold());
         std::process::exit(1);
     }
 
-    // If dry-run, just print and exit
-    if args.dry_run {
-        println!("\n{}", "Generated commit message(s):".green().bold());
-        for (i, msg) in messages.iter().enumerate() {
-            println!("\n{}. {}", i + 1, msg);
-        }
-        return Ok(());
+    if !force && Git::is_head_pushed()? {
+        eprintln!(
+            "{} HEAD has already been pushed to a remote branch",
+            "Error:".red().bold()
+        );
+        eprintln!(
+            "{}",
+            "Amending it rewrites published history. Use --force to amend anyway.".yellow()
+        );
+        std::process::exit(1);
     }
 
-    // Select message
-    let selected = if args.yes || messages.len() == 1 || !std::io::stdin().is_terminal() {
-        messages[0].clone()
-    } else {
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select a commit message")
-            .items(&messages)
-            .default(0)
-            .interact()?;
-        messages[selection].clone()
-    };
-
-    // Commit
-    Git::commit(&selected)?;
-    println!("\n{} {}", "✓".green().bold(), "Committed:".green());
-    println!("  {}", selected);
-
-    Ok(())
+    let diff = Git::get_amend_diff()?;
+    let message = Git::last_commit_message()?;
+    Ok((diff, Some(message)))
 }
 
-fn build_commit_prompt(diff: &str, config: &Config, commit_type: Option<&str>) -> String {
-    let language_instruction = match config.options.language {
-        Language::Ko => "Write the commit message in Korean.",
-        Language::En => "Write the commit message in English.",
-    };
+/// Generate commit message suggestions for the given diff
+pub async fn generate_messages(
+    client: &LlmClient,
+    diff: &str,
+    config: &Config,
+    commit_type: Option<&str>,
+    previous_message: Option<&str>,
+) -> Result<(Vec<String>, Option<Usage>), LlmError> {
+    let prompt = build_commit_prompt(diff, config, commit_type, previous_message);
+    let completion = client.generate(&prompt).await?;
+    let mut messages = parse_suggestions(&completion.text);
+    messages.truncate(config.options.suggestions.max(1));
+    Ok((messages, completion.usage))
+}
 
-    let format_instruction = match config.options.format {
+/// Instructions for writing a message in the configured commit format
+pub fn format_instruction(format: Format, commit_type: Option<&str>) -> String {
+    match format {
         Format::Conventional => {
             let type_hint = if let Some(t) = commit_type {
                 format!("Use '{}' as the commit type.", t)
@@ -204,8 +329,7 @@ IMPORTANT:
             )
         }
         Format::Gitmoji => {
-            format!(
-                r#"Use Gitmoji with Conventional Commits format. Follow this pattern EXACTLY:
+            r#"Use Gitmoji with Conventional Commits format. Follow this pattern EXACTLY:
   - emoji type: description
 
 Gitmoji mapping:
@@ -228,21 +352,58 @@ IMPORTANT:
   - Never omit emoji: ❌ feat: description
   - Use the correct emoji for the type
   - Keep type keyword after emoji for clarity"#
-            )
+                .to_string()
         }
         Format::Free => "Write a clear, concise commit message.".to_string(),
+    }
+}
+
+fn build_commit_prompt(
+    diff: &str,
+    config: &Config,
+    commit_type: Option<&str>,
+    previous_message: Option<&str>,
+) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the commit message in Korean.",
+        Language::En => "Write the commit message in English.",
+    };
+
+    let format_instruction = format_instruction(config.options.format, commit_type);
+
+    let count = config.options.suggestions.max(1);
+    let numbering = (1..=count)
+        .map(|i| format!("\"{}. \"", i))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let output_format = (1..=count)
+        .map(|i| format!("{}. type: description", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let suggestions_instruction = if count == 1 {
+        "Generate 1 suggestion".to_string()
+    } else {
+        format!("Generate {} different suggestions", count)
+    };
+
+    let previous_section = match previous_message {
+        Some(message) if !message.trim().is_empty() => format!(
+            "\nThe commit is being amended. Its current message is below; keep what is still accurate and fix what is not:\n```\n{}\n```\n",
+            message.trim()
+        ),
+        _ => String::new(),
     };
 
     format!(
         r#"You are a helpful assistant that generates Git commit messages based on the given diff.
-
+{previous_section}
 Instructions:
 - {language_instruction}
 - {format_instruction}
 - Keep the subject line under 72 characters
 - Be specific about what changed
-- Generate 3 different suggestions
-- Output ONLY the commit messages, one per line, starting with "1. ", "2. ", "3. "
+- {suggestions_instruction}
+- Output ONLY the commit messages, one per line, starting with {numbering}
 - Do NOT include any explanations, markdown formatting, or extra text
 
 Git diff:
@@ -251,16 +412,18 @@ Git diff:
 ```
 
 Output format (follow EXACTLY):
-1. type: description
-2. type(scope): description
-3. type: description"#,
+{output_format}"#,
+        previous_section = previous_section,
         language_instruction = language_instruction,
         format_instruction = format_instruction,
+        suggestions_instruction = suggestions_instruction,
+        numbering = numbering,
+        output_format = output_format,
         diff = truncate_diff(diff, 4000)
     )
 }
 
-fn truncate_diff(diff: &str, max_bytes: usize) -> &str {
+pub fn truncate_diff(diff: &str, max_bytes: usize) -> &str {
     if diff.len() <= max_bytes {
         diff
     } else {
@@ -273,20 +436,12 @@ fn truncate_diff(diff: &str, max_bytes: usize) -> &str {
     }
 }
 
-fn parse_suggestions(response: &str) -> Vec<String> {
+pub fn parse_suggestions(response: &str) -> Vec<String> {
     let mut suggestions = Vec::new();
 
     for line in response.lines() {
-        let trimmed = line.trim();
-        // Match lines starting with "1.", "2.", "3.", etc.
-        if let Some(rest) = trimmed
-            .strip_prefix("1.")
-            .or_else(|| trimmed.strip_prefix("2."))
-            .or_else(|| trimmed.strip_prefix("3."))
-            .or_else(|| trimmed.strip_prefix("1)"))
-            .or_else(|| trimmed.strip_prefix("2)"))
-            .or_else(|| trimmed.strip_prefix("3)"))
-        {
+        // Match lines starting with "1.", "2)", "10.", etc.
+        if let Some(rest) = strip_list_number(line.trim()) {
             let msg = rest.trim().trim_matches('`').trim_matches('"').trim();
             if !msg.is_empty() {
                 suggestions.push(msg.to_string());
@@ -305,6 +460,22 @@ fn parse_suggestions(response: &str) -> Vec<String> {
     suggestions
 }
 
+/// Strip a leading list marker such as "1." or "12)" from a line
+fn strip_list_number(line: &str) -> Option<&str> {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    let rest = line[digits..]
+        .strip_prefix('.')
+        .or_else(|| line[digits..].strip_prefix(')'))?;
+    // Avoid treating version numbers like "3.0" as list markers
+    if rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(rest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,6 +571,36 @@ mod tests {
         assert_eq!(suggestions[1], "fix: bug fix");
     }
 
+    #[test]
+    fn parse_suggestions_any_count() {
+        let response = (1..=12)
+            .map(|i| format!("{}) fix: change {}", i, i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let suggestions = parse_suggestions(&response);
+        assert_eq!(suggestions.len(), 12);
+        assert_eq!(suggestions[0], "fix: change 1");
+        assert_eq!(suggestions[11], "fix: change 12");
+    }
+
+    #[test]
+    fn parse_suggestions_ignores_version_numbers() {
+        let response = "3.0 release notes";
+        let suggestions = parse_suggestions(response);
+        assert_eq!(suggestions, vec!["3.0 release notes".to_string()]);
+    }
+
+    #[test]
+    fn build_commit_prompt_includes_previous_message() {
+        let config = Config::default();
+        let prompt = build_commit_prompt("diff", &config, None, Some("fix: old message\n"));
+        assert!(prompt.contains("being amended"));
+        assert!(prompt.contains("fix: old message"));
+
+        let prompt = build_commit_prompt("diff", &config, None, None);
+        assert!(!prompt.contains("being amended"));
+    }
+
     #[test]
     fn parse_suggestions_fallback() {
         let response = "feat: simple message";
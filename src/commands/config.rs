@@ -1,4 +1,4 @@
-use crate::config::{AutoStage, Config, Format, Language};
+use crate::config::{AutoStage, Config, ForgeKind, Format, IssueStyle, Language};
 use clap::Args;
 use colored::Colorize;
 
@@ -35,6 +35,66 @@ pub struct ConfigArgs {
     /// Set the Ollama server URL
     #[arg(long)]
     pub ollama_url: Option<String>,
+
+    /// Set the number of commit message suggestions
+    #[arg(long)]
+    pub suggestions: Option<usize>,
+
+    /// Set the sampling temperature
+    #[arg(long)]
+    pub temperature: Option<f32>,
+
+    /// Set the nucleus sampling probability
+    #[arg(long)]
+    pub top_p: Option<f32>,
+
+    /// Set the maximum number of tokens to generate
+    #[arg(long)]
+    pub max_tokens: Option<u32>,
+
+    /// Set the sampling seed (OpenAI and Ollama only)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Set the GitHub API URL (for GitHub Enterprise)
+    #[arg(long)]
+    pub github_api_url: Option<String>,
+
+    /// Set the self-hosted GitLab host (e.g. gitlab.example.com)
+    #[arg(long)]
+    pub gitlab_host: Option<String>,
+
+    /// Set the Gitea/Forgejo host (e.g. git.example.com)
+    #[arg(long)]
+    pub gitea_host: Option<String>,
+
+    /// Set the Bitbucket Server/Data Center host
+    #[arg(long)]
+    pub bitbucket_host: Option<String>,
+
+    /// Set the forge instead of detecting it from the remote URL
+    #[arg(long)]
+    pub forge: Option<ForgeKind>,
+
+    /// Set the issue key patterns (regex, repeatable; replaces the current list)
+    #[arg(long = "issue-pattern", value_name = "REGEX")]
+    pub issue_patterns: Vec<String>,
+
+    /// Set the Jira-style project keys, e.g. PROJ for PROJ-123 (repeatable; replaces the current list)
+    #[arg(long = "issue-project-key", value_name = "KEY")]
+    pub issue_project_keys: Vec<String>,
+
+    /// Set how issue keys are added to commit messages
+    #[arg(long)]
+    pub issue_style: Option<IssueStyle>,
+
+    /// Set the repositories summarized by `summary --repos` (repeatable; replaces the current list)
+    #[arg(long = "summary-repo", value_name = "PATH")]
+    pub summary_repos: Vec<String>,
+
+    /// Set the branch name pattern, e.g. "{type}/{ticket}-{slug}"
+    #[arg(long)]
+    pub branch_pattern: Option<String>,
 }
 
 pub async fn run(args: ConfigArgs) -> anyhow::Result<()> {
@@ -81,6 +141,92 @@ pub async fn run(args: ConfigArgs) -> anyhow::Result<()> {
         changed = true;
     }
 
+    if let Some(suggestions) = args.suggestions {
+        config.options.suggestions = suggestions.max(1);
+        changed = true;
+    }
+
+    if let Some(temperature) = args.temperature {
+        config.generation.temperature = Some(temperature);
+        changed = true;
+    }
+
+    if let Some(top_p) = args.top_p {
+        config.generation.top_p = Some(top_p);
+        changed = true;
+    }
+
+    if let Some(max_tokens) = args.max_tokens {
+        config.generation.max_tokens = Some(max_tokens);
+        changed = true;
+    }
+
+    if let Some(seed) = args.seed {
+        config.generation.seed = Some(seed);
+        changed = true;
+    }
+
+    if let Some(github_api_url) = args.github_api_url {
+        config.github.api_url = Some(github_api_url);
+        changed = true;
+    }
+
+    if let Some(gitlab_host) = args.gitlab_host {
+        config.gitlab.host = Some(gitlab_host);
+        changed = true;
+    }
+
+    if let Some(gitea_host) = args.gitea_host {
+        config.gitea.host = Some(gitea_host);
+        changed = true;
+    }
+
+    if let Some(bitbucket_host) = args.bitbucket_host {
+        config.bitbucket.host = Some(bitbucket_host);
+        changed = true;
+    }
+
+    if let Some(forge) = args.forge {
+        config.forge.kind = Some(forge);
+        changed = true;
+    }
+
+    if !args.issue_patterns.is_empty() {
+        if let Err(e) = crate::issues::compile(&args.issue_patterns) {
+            eprintln!("{} Invalid issue pattern: {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+        config.issues.patterns = args.issue_patterns;
+        changed = true;
+    }
+
+    if !args.issue_project_keys.is_empty() {
+        config.issues.project_keys = args.issue_project_keys;
+        changed = true;
+    }
+
+    if let Some(issue_style) = args.issue_style {
+        config.issues.style = issue_style;
+        changed = true;
+    }
+
+    if !args.summary_repos.is_empty() {
+        config.summary.repos = args.summary_repos;
+        changed = true;
+    }
+
+    if let Some(branch_pattern) = args.branch_pattern {
+        if !branch_pattern.contains("{slug}") {
+            eprintln!(
+                "{} The branch pattern must contain {{slug}}",
+                "Error:".red().bold()
+            );
+            std::process::exit(1);
+        }
+        config.branch.pattern = branch_pattern;
+        changed = true;
+    }
+
     if changed {
         config.save()?;
         println!("{}", "Configuration saved!".green());
@@ -126,6 +272,75 @@ pub async fn run(args: ConfigArgs) -> anyhow::Result<()> {
         config.options.auto_stage,
         "(ask, always, never)".dimmed()
     );
+    println!("  {} {}", "Suggestions:".cyan(), config.options.suggestions);
+    println!(
+        "  {} {}",
+        "Temperature:".cyan(),
+        display_optional(config.generation.temperature)
+    );
+    println!(
+        "  {} {}",
+        "Top P:".cyan(),
+        display_optional(config.generation.top_p)
+    );
+    println!(
+        "  {} {}",
+        "Max tokens:".cyan(),
+        display_optional(config.generation.max_tokens)
+    );
+    println!(
+        "  {} {}",
+        "Seed:".cyan(),
+        display_optional(config.generation.seed)
+    );
+    println!(
+        "  {} {} {}",
+        "Issue style:".cyan(),
+        config.issues.style,
+        "(footer, prefix, off)".dimmed()
+    );
+    println!(
+        "  {} {}",
+        "Issue patterns:".cyan(),
+        config.issues.patterns.join("  ")
+    );
+    if !config.issues.project_keys.is_empty() {
+        println!(
+            "  {} {}",
+            "Issue project keys:".cyan(),
+            config.issues.project_keys.join(", ")
+        );
+    }
+    if let Some(ref api_url) = config.github.api_url {
+        println!("  {} {}", "GitHub API:".cyan(), api_url);
+    }
+    println!(
+        "  {} {} {}",
+        "Forge:".cyan(),
+        config
+            .forge
+            .kind
+            .map(|kind| kind.to_string())
+            .unwrap_or_else(|| "(detect from remote)".to_string()),
+        "(github, gitlab, gitea, bitbucket-cloud, bitbucket-server)".dimmed()
+    );
+    if let Some(ref host) = config.gitlab.host {
+        println!("  {} {}", "GitLab host:".cyan(), host);
+    }
+    if let Some(ref host) = config.gitea.host {
+        println!("  {} {}", "Gitea host:".cyan(), host);
+    }
+    if let Some(ref host) = config.bitbucket.host {
+        println!("  {} {}", "Bitbucket host:".cyan(), host);
+    }
+    println!("  {} {}", "Branch pattern:".cyan(), config.branch.pattern);
+    if !config.summary.repos.is_empty() {
+        println!(
+            "  {} {}",
+            "Summary repos:".cyan(),
+            config.summary.repos.join("  ")
+        );
+    }
     println!("─────────────────────────────────");
     println!(
         "\n  Config file: {}",
@@ -134,3 +349,9 @@ pub async fn run(args: ConfigArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn display_optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "(provider default)".to_string())
+}
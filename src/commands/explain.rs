@@ -0,0 +1,123 @@
+use super::commit::truncate_diff;
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::Git;
+use crate::llm::{ChatMessage, LlmClient};
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Input};
+use std::io::{IsTerminal, Write};
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Commit or range to explain (e.g. HEAD~3..HEAD)
+    #[arg(default_value = "HEAD")]
+    pub rev: String,
+
+    /// Print the explanation without asking for follow-up questions
+    #[arg(long)]
+    pub no_follow_up: bool,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+pub async fn run(args: ExplainArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let show = match Git::show(&args.rev) {
+        Ok(s) if !s.trim().is_empty() => s,
+        Ok(_) => {
+            eprintln!("{}", format!("No commits in {}.", args.rev).yellow());
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    println!("{} {}\n", "Explaining:".dimmed(), args.rev.cyan());
+    let mut messages = vec![ChatMessage::user(build_explain_prompt(&show, &config))];
+    let reply = stream_reply(&client, &messages).await?;
+    messages.push(ChatMessage::assistant(reply));
+
+    if args.no_follow_up || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    loop {
+        let question: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Follow-up question (empty to quit)")
+            .allow_empty(true)
+            .interact_text()?;
+        let question = question.trim();
+        if question.is_empty() {
+            break;
+        }
+
+        println!();
+        messages.push(ChatMessage::user(question));
+        let reply = stream_reply(&client, &messages).await?;
+        messages.push(ChatMessage::assistant(reply));
+    }
+
+    Ok(())
+}
+
+/// Print the reply as it arrives and return it in full
+async fn stream_reply(client: &LlmClient, messages: &[ChatMessage]) -> anyhow::Result<String> {
+    let mut stdout = std::io::stdout();
+    let completion = client
+        .chat(messages, &mut |text: &str| {
+            print!("{}", text);
+            let _ = stdout.flush();
+        })
+        .await?;
+    if !completion.text.ends_with('\n') {
+        println!();
+    }
+    println!();
+    Ok(completion.text)
+}
+
+fn build_explain_prompt(show: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Answer in Korean.",
+        Language::En => "Answer in English.",
+    };
+
+    format!(
+        r#"You are helping a developer who is new to this codebase understand a change.
+Below is the output of `git show` for one or more commits: the commit messages and their diffs.
+
+Instructions:
+- {language_instruction}
+- Explain the change in three markdown sections:
+  ## Intent: why the change was made and what problem it solves.
+  ## Key changes: the important changes, file by file or by concern, in a few bullets.
+  ## Possible side effects: behavior changes, risks, or things that might break elsewhere.
+- Be concrete and refer to files, functions and types by name. Don't restate the diff line by line.
+- Afterwards, answer follow-up questions about the change concisely.
+
+git show output (truncated if too long):
+```
+{show}
+```"#,
+        language_instruction = language_instruction,
+        show = truncate_diff(show, 16000)
+    )
+}
@@ -0,0 +1,272 @@
+use super::commit::{generate_messages, link_issues};
+use crate::config::Config;
+use crate::git::Git;
+use crate::llm::LlmClient;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+const HOOK_NAME: &str = "prepare-commit-msg";
+const BACKUP_SUFFIX: &str = ".pre-git-ai";
+const HOOK_MARKER: &str = "# git-ai prepare-commit-msg hook";
+
+#[derive(Args)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub action: HookAction,
+}
+
+#[derive(Subcommand)]
+pub enum HookAction {
+    /// Install the prepare-commit-msg hook
+    Install,
+
+    /// Remove the hook and restore any hook it replaced
+    Uninstall,
+
+    /// Show whether the hook is installed
+    Status,
+
+    /// Run in hook mode (invoked by git)
+    #[command(hide = true)]
+    Run {
+        /// Path to the commit message file
+        message_file: PathBuf,
+
+        /// Source of the commit message (message, template, merge, squash, commit)
+        source: Option<String>,
+
+        /// Commit SHA when amending or reusing a message
+        sha: Option<String>,
+    },
+}
+
+pub async fn run(args: HookArgs) -> anyhow::Result<()> {
+    match args.action {
+        HookAction::Install => install(),
+        HookAction::Uninstall => uninstall(),
+        HookAction::Status => status(),
+        HookAction::Run {
+            message_file,
+            source,
+            sha: _,
+        } => {
+            // Never block the commit: report failures and let git continue
+            if let Err(e) = run_hook(&message_file, source.as_deref()).await {
+                eprintln!("{} {}", "git-ai:".yellow(), e);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn install() -> anyhow::Result<()> {
+    let dir = Git::hooks_dir()?;
+    let hook = dir.join(HOOK_NAME);
+    let backup = backup_path(&hook);
+
+    if is_git_ai_hook(&hook) {
+        println!("{}", "Hook is already installed.".dimmed());
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&dir)?;
+
+    // Keep an existing hook and chain to it from ours
+    if hook.exists() {
+        if backup.exists() {
+            anyhow::bail!(
+                "Both {} and {} exist. Remove one of them first.",
+                hook.display(),
+                backup.display()
+            );
+        }
+        std::fs::rename(&hook, &backup)?;
+        println!(
+            "{} {}",
+            "Existing hook moved to".dimmed(),
+            backup.display().to_string().cyan()
+        );
+    }
+
+    std::fs::write(&hook, hook_script())?;
+    make_executable(&hook)?;
+
+    println!("{} {}", "✓".green().bold(), "Installed hook:".green());
+    println!("  {}", hook.display());
+    Ok(())
+}
+
+fn uninstall() -> anyhow::Result<()> {
+    let dir = Git::hooks_dir()?;
+    let hook = dir.join(HOOK_NAME);
+    let backup = backup_path(&hook);
+
+    if !is_git_ai_hook(&hook) {
+        if hook.exists() {
+            eprintln!(
+                "{}",
+                "The installed prepare-commit-msg hook was not created by git-ai. Leaving it alone."
+                    .yellow()
+            );
+        } else {
+            println!("{}", "Hook is not installed.".dimmed());
+        }
+        return Ok(());
+    }
+
+    std::fs::remove_file(&hook)?;
+    if backup.exists() {
+        std::fs::rename(&backup, &hook)?;
+        println!("{}", "Restored previous hook.".dimmed());
+    }
+
+    println!("{} {}", "✓".green().bold(), "Uninstalled hook.".green());
+    Ok(())
+}
+
+fn status() -> anyhow::Result<()> {
+    let dir = Git::hooks_dir()?;
+    let hook = dir.join(HOOK_NAME);
+    let backup = backup_path(&hook);
+
+    let state = if is_git_ai_hook(&hook) {
+        "installed".green()
+    } else if hook.exists() {
+        "not installed (another hook is present)".yellow()
+    } else {
+        "not installed".yellow()
+    };
+
+    println!("  {} {}", "Status:".cyan(), state);
+    println!("  {} {}", "Hook:".cyan(), hook.display());
+    if backup.exists() {
+        println!("  {} {}", "Chained:".cyan(), backup.display());
+    }
+    Ok(())
+}
+
+async fn run_hook(message_file: &Path, source: Option<&str>) -> anyhow::Result<()> {
+    if !should_generate(source) {
+        return Ok(());
+    }
+
+    let existing = std::fs::read_to_string(message_file)?;
+    if has_message(&existing) {
+        return Ok(());
+    }
+
+    let diff = match Git::get_staged_diff() {
+        Ok(d) => d,
+        Err(crate::git::GitError::NoStagedChanges) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut config = Config::load()?;
+    config.options.suggestions = 1;
+
+    eprintln!("{}", "git-ai: generating commit message...".dimmed());
+    let client = LlmClient::from_config(&config)?;
+    let (messages, _) = generate_messages(&client, &diff, &config, None, None).await?;
+    let messages = link_issues(messages, &config);
+
+    if let Some(message) = messages.first() {
+        std::fs::write(message_file, format!("{}\n{}", message, existing))?;
+    }
+    Ok(())
+}
+
+/// Whether the hook should write a message for the given commit source.
+/// Merges, amends/reused messages (`commit`), squashes and `-m` are left untouched.
+fn should_generate(source: Option<&str>) -> bool {
+    matches!(source, None | Some("template"))
+}
+
+/// Whether the message file already has non-comment content
+fn has_message(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| !line.trim().is_empty() && !line.starts_with('#'))
+}
+
+fn hook_script() -> String {
+    format!(
+        r#"#!/bin/sh
+{marker}
+# Installed by `git ai hook install`. Remove with `git ai hook uninstall`.
+
+HOOK_DIR=$(dirname "$0")
+if [ -x "$HOOK_DIR/{name}{suffix}" ]; then
+    "$HOOK_DIR/{name}{suffix}" "$@" || exit $?
+fi
+
+git-ai hook run "$@" || true
+"#,
+        marker = HOOK_MARKER,
+        name = HOOK_NAME,
+        suffix = BACKUP_SUFFIX
+    )
+}
+
+fn is_git_ai_hook(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .map(|content| content.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+fn backup_path(hook: &Path) -> PathBuf {
+    let mut name = hook.as_os_str().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    PathBuf::from(name)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_generate_skips_merge_amend_and_message() {
+        assert!(should_generate(None));
+        assert!(should_generate(Some("template")));
+        assert!(!should_generate(Some("message")));
+        assert!(!should_generate(Some("merge")));
+        assert!(!should_generate(Some("squash")));
+        assert!(!should_generate(Some("commit")));
+    }
+
+    #[test]
+    fn has_message_ignores_comments() {
+        let content = "\n# Please enter the commit message\n#\n# On branch main\n";
+        assert!(!has_message(content));
+        assert!(has_message("feat: add hook\n# comment\n"));
+    }
+
+    #[test]
+    fn hook_script_chains_backup() {
+        let script = hook_script();
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains(HOOK_MARKER));
+        assert!(script.contains("prepare-commit-msg.pre-git-ai"));
+        assert!(script.contains("git-ai hook run \"$@\""));
+    }
+
+    #[test]
+    fn backup_path_appends_suffix() {
+        let hook = Path::new(".git/hooks/prepare-commit-msg");
+        assert_eq!(
+            backup_path(hook),
+            PathBuf::from(".git/hooks/prepare-commit-msg.pre-git-ai")
+        );
+    }
+}
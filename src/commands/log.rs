@@ -0,0 +1,321 @@
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::Git;
+use crate::llm::{LlmClient, Usage};
+use crate::output::{print_json, OutputFormat};
+use crate::search::{Index, Match};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// Texts sent per embedding request
+const EMBEDDING_BATCH: usize = 64;
+
+#[derive(Args)]
+pub struct LogArgs {
+    /// Question about the history, e.g. "when did we change the retry logic?"
+    pub question: String,
+
+    /// Number of commits to show
+    #[arg(short = 'n', long, default_value_t = 5)]
+    pub limit: usize,
+
+    /// Show the matches without generating summaries
+    #[arg(long)]
+    pub no_summary: bool,
+
+    /// Rebuild the index from scratch
+    #[arg(long)]
+    pub reindex: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// A match in `log --output json`
+#[derive(Serialize)]
+struct MatchOutput<'a> {
+    hash: &'a str,
+    date: &'a str,
+    author: &'a str,
+    subject: &'a str,
+    score: f32,
+    summary: Option<&'a str>,
+}
+
+/// JSON document printed by `log --output json`
+#[derive(Serialize)]
+struct LogOutput<'a> {
+    question: &'a str,
+    matches: Vec<MatchOutput<'a>>,
+    provider: Option<&'a str>,
+    model: Option<&'a str>,
+    usage: Option<Usage>,
+    duration_ms: Option<u128>,
+}
+
+pub async fn run(args: LogArgs) -> anyhow::Result<()> {
+    let output = args.output;
+
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let path = Index::path()?;
+    let mut index = if args.reindex {
+        Index::default()
+    } else {
+        Index::load(&path)
+    };
+    output.status("Updating the history index...".dimmed());
+    let added = index.update()?;
+    if added > 0 {
+        output.status(format!("Indexed {} new commit(s).", added).dimmed());
+    }
+    let scope: HashSet<String> = Git::rev_list("HEAD")?.into_iter().collect();
+
+    let client = if args.no_summary && !config.log.embeddings {
+        None
+    } else {
+        match LlmClient::from_config(&config) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                eprintln!(
+                    "{}",
+                    "Run 'git ai config' to configure your LLM provider.".yellow()
+                );
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let query_embedding = match client {
+        Some(ref client) if config.log.embeddings => {
+            match embed_index(client, &config, &mut index, &scope, &args.question, output).await {
+                Ok(embedding) => Some(embedding),
+                Err(e) => {
+                    eprintln!(
+                        "{} Embeddings unavailable, ranking by keywords only: {}",
+                        "Warning:".yellow().bold(),
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    index.save(&path)?;
+
+    let matches = index.search(
+        &args.question,
+        query_embedding.as_deref(),
+        &scope,
+        args.limit,
+    );
+    if matches.is_empty() {
+        eprintln!("{}", "No matching commits found.".yellow());
+        std::process::exit(1);
+    }
+
+    let mut summaries = HashMap::new();
+    let mut usage = None;
+    let mut duration = None;
+    if let Some(client) = client.as_ref().filter(|_| !args.no_summary) {
+        output.status("Summarizing matches...".dimmed());
+        let started = Instant::now();
+        let completion = client
+            .generate(&build_log_prompt(&args.question, &matches, &config))
+            .await?;
+        duration = Some(started.elapsed().as_millis());
+        usage = completion.usage;
+        summaries = parse_summaries(&completion.text, &matches);
+    }
+
+    if output.is_json() {
+        print_json(&LogOutput {
+            question: &args.question,
+            matches: matches
+                .iter()
+                .map(|m| MatchOutput {
+                    hash: &m.commit.hash,
+                    date: &m.commit.date,
+                    author: &m.commit.author,
+                    subject: &m.commit.subject,
+                    score: m.score,
+                    summary: summaries.get(m.commit.hash.as_str()).map(String::as_str),
+                })
+                .collect(),
+            provider: client.as_ref().map(|c| c.provider_name()),
+            model: client.as_ref().map(|c| c.model()),
+            usage,
+            duration_ms: duration,
+        })?;
+    } else {
+        for m in &matches {
+            println!(
+                "\n{} {} {}",
+                m.commit.short_hash().yellow(),
+                m.commit.date.get(..10).unwrap_or(&m.commit.date).dimmed(),
+                m.commit.author.dimmed()
+            );
+            println!("  {}", m.commit.subject.bold());
+            if let Some(summary) = summaries.get(m.commit.hash.as_str()) {
+                println!("  {}", summary);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Embed the commits in scope that have no embedding yet, and the question.
+/// Stored embeddings are dropped when the embedding model changes.
+async fn embed_index(
+    client: &LlmClient,
+    config: &Config,
+    index: &mut Index,
+    scope: &HashSet<String>,
+    question: &str,
+    output: OutputFormat,
+) -> anyhow::Result<Vec<f32>> {
+    let Some(model) = client.embedding_model(config.log.embedding_model.as_deref()) else {
+        anyhow::bail!("no embedding model for {}", client.provider_name());
+    };
+    if index.embedding_model.as_deref() != Some(&model) {
+        for commit in &mut index.commits {
+            commit.embedding = None;
+        }
+        index.embedding_model = Some(model.clone());
+    }
+
+    let mut missing: Vec<_> = index
+        .commits
+        .iter_mut()
+        .filter(|c| c.embedding.is_none() && scope.contains(&c.hash))
+        .collect();
+    if !missing.is_empty() {
+        output.status(format!("Embedding {} commit(s)...", missing.len()).dimmed());
+    }
+    for batch in missing.chunks_mut(EMBEDDING_BATCH) {
+        let texts: Vec<String> = batch.iter().map(|c| c.embedding_text()).collect();
+        let embeddings = client.embed(&texts, &model).await?;
+        for (commit, embedding) in batch.iter_mut().zip(embeddings) {
+            commit.embedding = Some(embedding);
+        }
+    }
+
+    let mut query = client.embed(&[question.to_string()], &model).await?;
+    query
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("empty embedding response"))
+}
+
+fn build_log_prompt(question: &str, matches: &[Match], config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the summaries in Korean.",
+        Language::En => "Write the summaries in English.",
+    };
+
+    let commits: Vec<String> = matches
+        .iter()
+        .map(|m| {
+            let c = m.commit;
+            let mut text = format!(
+                "[{}] {} ({})\n{}",
+                c.short_hash(),
+                c.subject,
+                c.date,
+                c.body
+            );
+            text.truncate(text.trim_end().len());
+            let files: Vec<&str> = c.files.iter().take(10).map(String::as_str).collect();
+            text.push_str(&format!("\nFiles: {}", files.join(", ")));
+            if !c.contexts.is_empty() {
+                let contexts: Vec<&str> = c.contexts.iter().take(10).map(String::as_str).collect();
+                text.push_str(&format!("\nChanged in: {}", contexts.join("; ")));
+            }
+            text
+        })
+        .collect();
+
+    format!(
+        r#"A developer is searching the Git history of a project with this question:
+"{question}"
+
+Below are the best matching commits.
+
+Instructions:
+- {language_instruction}
+- For each commit, write one sentence on what it changed, focused on what is relevant to the question.
+- Output one line per commit in this exact format, in the given order:
+  <hash>: <summary>
+- Do NOT include any other text.
+
+Commits:
+{commits}"#,
+        question = question,
+        language_instruction = language_instruction,
+        commits = commits.join("\n\n")
+    )
+}
+
+/// Map `<hash>: <summary>` lines back to the matched commits
+fn parse_summaries(response: &str, matches: &[Match]) -> HashMap<String, String> {
+    let mut summaries = HashMap::new();
+    for line in response.lines() {
+        let line = line.trim().trim_start_matches(['-', '*', ' ']);
+        let Some((hash, summary)) = line.split_once(':') else {
+            continue;
+        };
+        let hash = hash.trim().trim_matches(['[', ']', '`']);
+        if hash.len() < 4 {
+            continue;
+        }
+        if let Some(m) = matches.iter().find(|m| m.commit.hash.starts_with(hash)) {
+            summaries.insert(m.commit.hash.clone(), summary.trim().to_string());
+        }
+    }
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::Commit;
+    use crate::search::IndexedCommit;
+
+    fn commit(hash: &str) -> IndexedCommit {
+        IndexedCommit::new(Commit::test("fix: retry").with_hash(hash), "")
+    }
+
+    #[test]
+    fn parse_summaries_by_hash_prefix() {
+        let first = commit("1a2b3c4d5e6f");
+        let second = commit("9f8e7d6c5b4a");
+        let matches = vec![
+            Match {
+                commit: &first,
+                score: 1.0,
+            },
+            Match {
+                commit: &second,
+                score: 0.5,
+            },
+        ];
+
+        let summaries = parse_summaries(
+            "- [1a2b3c4]: Added exponential backoff.\n9f8e7d6: Raised the retry limit to 5.\nunrelated: text",
+            &matches,
+        );
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries["1a2b3c4d5e6f"], "Added exponential backoff.");
+        assert_eq!(summaries["9f8e7d6c5b4a"], "Raised the retry limit to 5.");
+    }
+}
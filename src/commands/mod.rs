@@ -1,3 +1,57 @@
+pub mod branch;
+pub mod changelog;
 pub mod commit;
 pub mod config;
+pub mod explain;
+pub mod hook;
+pub mod log;
 pub mod pr;
+pub mod release;
+pub mod resolve;
+pub mod review;
+pub mod split;
+pub mod squash;
+pub mod stash;
+pub mod summary;
+pub mod tag;
+
+use crate::config::GenerationConfig;
+use clap::Args;
+
+/// Sampling options shared by commands that call the LLM
+#[derive(Args)]
+pub struct GenerationArgs {
+    /// Sampling temperature (overrides config)
+    #[arg(long)]
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling probability (overrides config)
+    #[arg(long)]
+    pub top_p: Option<f32>,
+
+    /// Maximum number of tokens to generate (overrides config)
+    #[arg(long)]
+    pub max_tokens: Option<u32>,
+
+    /// Seed for reproducible sampling, where supported (overrides config)
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+impl GenerationArgs {
+    /// Apply command-line overrides on top of the configured values
+    pub fn apply(&self, generation: &mut GenerationConfig) {
+        if let Some(temperature) = self.temperature {
+            generation.temperature = Some(temperature);
+        }
+        if let Some(top_p) = self.top_p {
+            generation.top_p = Some(top_p);
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            generation.max_tokens = Some(max_tokens);
+        }
+        if let Some(seed) = self.seed {
+            generation.seed = Some(seed);
+        }
+    }
+}
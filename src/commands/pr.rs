@@ -1,9 +1,22 @@
+use super::commit::truncate_diff;
+use super::GenerationArgs;
+use crate::codeowners::{CodeOwners, Ownership};
 use crate::config::{Config, Language};
+use crate::forge::{self, Forge, NewPullRequest, PullRequest, RemoteRepo};
 use crate::git::Git;
-use crate::llm::LlmClient;
+use crate::issues;
+use crate::llm::{LlmClient, Usage};
+use crate::output::{print_json, OutputFormat};
+use crate::risk::RiskReport;
 use arboard::Clipboard;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+use serde::Serialize;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Args)]
 pub struct PrArgs {
@@ -11,27 +24,273 @@ pub struct PrArgs {
     #[arg(long, short = 'b')]
     pub base: Option<String>,
 
-    /// Copy the generated PR description to clipboard
-    #[arg(long, short = 'c')]
-    pub copy: bool,
+    /// Remote holding the base branch (default: upstream if it exists, else --remote)
+    #[arg(long, value_name = "REMOTE")]
+    pub base_remote: Option<String>,
+
+    /// Fetch the base branch before comparing
+    #[arg(long, conflicts_with = "local_base")]
+    pub fetch: bool,
+
+    /// Compare against the local base branch instead of its remote-tracking branch
+    #[arg(long)]
+    pub local_base: bool,
+
+    /// Copy the generated PR description to clipboard (all, title or body)
+    #[arg(
+        long,
+        short = 'c',
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "all"
+    )]
+    pub copy: Option<CopyTarget>,
+
+    /// Print only the PR title
+    #[arg(long, conflicts_with = "body_only")]
+    pub title_only: bool,
+
+    /// Print only the PR body
+    #[arg(long)]
+    pub body_only: bool,
+
+    /// Push the branch if needed and open a pull request on the forge
+    /// (GitHub, GitLab, Gitea/Forgejo or Bitbucket), or update the open one
+    #[arg(long)]
+    pub create: bool,
+
+    /// Open the pull request as a draft
+    #[arg(long, requires = "create")]
+    pub draft: bool,
+
+    /// Label to add to the pull request (repeatable)
+    #[arg(long = "label", value_name = "LABEL", requires = "create")]
+    pub labels: Vec<String>,
+
+    /// User or team (org/team) to request a review from (repeatable)
+    #[arg(long = "reviewer", value_name = "REVIEWER", requires = "create")]
+    pub reviewers: Vec<String>,
+
+    /// Don't request reviews from the code owners of the changed files
+    #[arg(long, requires = "create")]
+    pub no_owners: bool,
+
+    /// Don't add the risk section or suggest labels
+    #[arg(long)]
+    pub no_risk: bool,
+
+    /// Regenerate the description of the open pull request for this branch,
+    /// keeping sections between <!-- git-ai:keep --> and <!-- /git-ai:keep -->
+    #[arg(long, conflicts_with_all = ["create", "title_only", "body_only"])]
+    pub update: bool,
+
+    /// Apply the update without asking for confirmation
+    #[arg(long, short = 'y', requires = "update")]
+    pub yes: bool,
+
+    /// Pull request template to fill in (default: the repository's template)
+    #[arg(long, value_name = "PATH", conflicts_with = "no_template")]
+    pub template: Option<PathBuf>,
+
+    /// Ignore the repository's pull request template
+    #[arg(long)]
+    pub no_template: bool,
+
+    /// Remote to push to and create the pull request on
+    #[arg(long, default_value = "origin")]
+    pub remote: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+impl PrArgs {
+    fn base_options(&self) -> BaseOptions<'_> {
+        BaseOptions {
+            base: self.base.as_deref(),
+            base_remote: self.base_remote.as_deref(),
+            remote: &self.remote,
+            fetch: self.fetch,
+            local_base: self.local_base,
+        }
+    }
+}
+
+/// Where the base branch of a pull request lives, shared by `pr` and `review`
+pub struct BaseOptions<'a> {
+    /// Base branch given on the command line, possibly as `remote/branch`
+    pub base: Option<&'a str>,
+    /// Remote holding the base branch
+    pub base_remote: Option<&'a str>,
+    /// Remote the branch is pushed to
+    pub remote: &'a str,
+    pub fetch: bool,
+    pub local_base: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CopyTarget {
+    /// Title and body
+    All,
+    /// Title only
+    Title,
+    /// Body only
+    Body,
+}
+
+/// Maximum recommended length of a PR title
+const MAX_TITLE_LEN: usize = 72;
+
+/// Directories searched for `pull_request_template.md` or a
+/// `PULL_REQUEST_TEMPLATE/` directory, in order (names are case-insensitive)
+const TEMPLATE_DIRS: [&str; 4] = [".github", "", "docs", ".gitea"];
+
+/// GitLab keeps merge request templates in their own directory
+const GITLAB_TEMPLATE_DIR: &str = ".gitlab/merge_request_templates";
+
+/// Offer to fetch the base branch when it was last fetched longer ago than this
+const STALE_FETCH_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Sentinels around human-written sections that `--update` preserves
+const KEEP_START: &str = "<!-- git-ai:keep -->";
+const KEEP_END: &str = "<!-- /git-ai:keep -->";
+
+/// A generated pull request description split into title and body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrDescription {
+    pub title: String,
+    pub body: String,
+}
+
+impl PrDescription {
+    /// Parse the raw LLM response. The first non-empty line is the title;
+    /// "Title:" prefixes, markdown headings and wrapping quotes are removed.
+    pub fn parse(response: &str) -> Self {
+        let trimmed = strip_code_fence(response.trim());
+        let (title, body) = match trimmed.split_once('\n') {
+            Some((title, body)) => (title, body),
+            None => (trimmed, ""),
+        };
+
+        let mut body = body.trim();
+        for prefix in ["Description:", "**Description:**", "Body:", "**Body:**"] {
+            if let Some(rest) = body.strip_prefix(prefix) {
+                body = rest.trim();
+            }
+        }
+
+        Self {
+            title: clean_title(title),
+            body: body.to_string(),
+        }
+    }
+
+    /// Check the description for problems worth warning about
+    pub fn validate(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let title_len = self.title.chars().count();
+        if self.title.is_empty() {
+            warnings.push("The generated title is empty.".to_string());
+        } else if title_len > MAX_TITLE_LEN {
+            warnings.push(format!(
+                "The title is {} characters long (recommended: {} or fewer).",
+                title_len, MAX_TITLE_LEN
+            ));
+        }
+        if self.body.is_empty() {
+            warnings.push("The generated body is empty.".to_string());
+        }
+        warnings
+    }
+
+    /// Title and body joined for display or copying
+    pub fn to_markdown(&self) -> String {
+        if self.body.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n\n{}", self.title, self.body)
+        }
+    }
+}
+
+/// JSON document printed by `pr --output json`
+#[derive(Serialize)]
+struct PrOutput<'a> {
+    title: &'a str,
+    body: &'a str,
+    base: &'a str,
+    /// Ref the branch was compared against, e.g. origin/main
+    base_ref: &'a str,
+    head: &'a str,
+    provider: &'a str,
+    model: &'a str,
+    usage: Option<Usage>,
+    duration_ms: u128,
+    /// Code owners of the changed files
+    owners: &'a [String],
+    /// Changed files without a code owner
+    unowned_paths: &'a [String],
+    risk: Option<&'a RiskReport>,
+    pull_request: Option<&'a PullRequest>,
 }
 
 pub async fn run(args: PrArgs) -> anyhow::Result<()> {
+    let output = args.output;
+    // Keep stdout clean for scripts when printing JSON or a single field
+    let progress_to_stderr = output.is_json() || args.title_only || args.body_only;
+    let status = |message: String| {
+        if progress_to_stderr {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
+
+    // Load config
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let current_branch = Git::current_branch()?;
+
+    // When updating, the open pull request's target is the default base
+    let existing = if args.update {
+        match find_pull_request(&args, &config, &current_branch).await {
+            Ok(found) => Some(found),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
     // Determine base branch
     let base = args
         .base
+        .clone()
+        .or_else(|| {
+            existing
+                .as_ref()
+                .map(|(_, pr)| pr.base.clone())
+                .filter(|base| !base.is_empty())
+        })
         .unwrap_or_else(|| Git::default_branch().unwrap_or_else(|_| "main".to_string()));
+    let (base, base_ref) = resolve_base(&args.base_options(), &base, &status);
 
-    let current_branch = Git::current_branch()?;
-    println!(
+    status(format!(
         "{} {} → {}",
         "Comparing:".dimmed(),
-        base.cyan(),
+        base_ref.cyan(),
         current_branch.green()
-    );
+    ));
 
     // Get diff and commit log
-    let diff = match Git::get_branch_diff(&base) {
+    let diff = match Git::get_branch_diff(&base_ref) {
         Ok(d) => d,
         Err(e) => {
             eprintln!("{} {}", "Error:".red().bold(), e);
@@ -39,21 +298,45 @@ pub async fn run(args: PrArgs) -> anyhow::Result<()> {
         }
     };
 
-    let commits = Git::get_commit_log(&base).unwrap_or_default();
+    let commits: Vec<String> = Git::get_commit_log(Some(&base_ref), "HEAD")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|commit| commit.subject)
+        .collect();
 
     if diff.trim().is_empty() && commits.is_empty() {
         eprintln!("{}", "No changes found compared to base branch.".yellow());
         std::process::exit(1);
     }
 
-    // Load config
-    let config = Config::load()?;
+    // Find the code owners of the changed files
+    let ownership = code_ownership(&base_ref);
+
+    // Find the pull request template to fill in
+    let template = match load_template(&args) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    if let Some((ref path, _)) = template {
+        status(format!("{} {}", "Template:".dimmed(), path.display()));
+    }
 
     // Build prompt
-    let prompt = build_pr_prompt(&diff, &commits, &config);
+    let template_text = template.as_ref().map(|(_, text)| text.as_str());
+    let risk = if args.no_risk {
+        None
+    } else {
+        Git::get_diff_stats(&base_ref)
+            .ok()
+            .map(|stats| RiskReport::assess(&stats, &config.risk))
+    };
+    let prompt = build_pr_prompt(&diff, &commits, &config, template_text, risk.as_ref());
 
     // Get LLM client
-    println!("{}", "Generating PR description...".dimmed());
+    status("Generating PR description...".dimmed().to_string());
     let client = match LlmClient::from_config(&config) {
         Ok(c) => c,
         Err(e) => {
@@ -67,19 +350,161 @@ pub async fn run(args: PrArgs) -> anyhow::Result<()> {
     };
 
     // Generate PR description
-    let response = client.generate(&prompt).await?;
+    let started = Instant::now();
+    let completion = client.generate(&prompt).await?;
+    let duration = started.elapsed();
+    let mut description = PrDescription::parse(&completion.text);
+    if let Some(ref risk) = risk {
+        description.body = add_risk_summary(&description.body, risk);
+    }
+    if config.issues.link_pull_requests {
+        match issues::detect(&config.issues, Some(&base_ref)) {
+            Ok(keys) => {
+                description.body = issues::apply_to_pr_body(
+                    &description.body,
+                    &keys,
+                    &config.issues.closing_keyword,
+                );
+            }
+            Err(e) => eprintln!(
+                "{} Invalid issue pattern: {}",
+                "Warning:".yellow().bold(),
+                e
+            ),
+        }
+    }
+
+    for warning in description.validate() {
+        eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+    }
+    if let Some(text) = template_text {
+        for heading in missing_headings(text, &description.body) {
+            eprintln!(
+                "{} The template heading \"{}\" is missing.",
+                "Warning:".yellow().bold(),
+                heading
+            );
+        }
+    }
+
+    // Open or update the pull request if requested; `action` describes what happened
+    let created = if let Some((forge, existing)) = &existing {
+        // Keep the human-chosen title and only refresh the body
+        description = PrDescription {
+            title: existing.title.clone(),
+            body: merge_kept_sections(&existing.body, &description.body),
+        };
+        match update_pull_request(
+            &args,
+            forge.as_ref(),
+            existing,
+            &description,
+            &current_branch,
+            &status,
+        )
+        .await
+        {
+            Ok(updated) => Some(updated),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else if args.create {
+        let owners = ownership
+            .as_ref()
+            .map(|(_, ownership)| ownership.owners.as_slice())
+            .unwrap_or_default();
+        match create_pull_request(
+            &args,
+            &config,
+            &description,
+            owners,
+            &base,
+            &current_branch,
+            &status,
+        )
+        .await
+        {
+            Ok(published) => Some(published),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
 
     // Print the result
-    println!("\n{}", "━".repeat(60).dimmed());
-    println!("{}", response);
-    println!("{}", "━".repeat(60).dimmed());
+    if output.is_json() {
+        print_json(&PrOutput {
+            title: &description.title,
+            body: &description.body,
+            base: &base,
+            base_ref: &base_ref,
+            head: &current_branch,
+            provider: client.provider_name(),
+            model: client.model(),
+            usage: completion.usage,
+            duration_ms: duration.as_millis(),
+            owners: ownership
+                .as_ref()
+                .map(|(_, ownership)| ownership.owners.as_slice())
+                .unwrap_or_default(),
+            unowned_paths: ownership
+                .as_ref()
+                .map(|(_, ownership)| ownership.unowned.as_slice())
+                .unwrap_or_default(),
+            risk: risk.as_ref(),
+            pull_request: created.as_ref().map(|(pr, _)| pr),
+        })?;
+    } else if args.title_only {
+        println!("{}", description.title);
+    } else if args.body_only {
+        println!("{}", description.body);
+    } else if !args.update {
+        println!("\n{}", "━".repeat(60).dimmed());
+        println!("{}", description.title.bold());
+        if !description.body.is_empty() {
+            println!("\n{}", description.body);
+        }
+        println!("{}", "━".repeat(60).dimmed());
+    }
+
+    if let Some((path, ownership)) = ownership.as_ref().filter(|_| !progress_to_stderr) {
+        print_ownership(path, ownership);
+    }
+    if let Some(risk) = risk.as_ref().filter(|_| !progress_to_stderr) {
+        if !risk.labels.is_empty() {
+            println!(
+                "\n{} {}",
+                "Suggested labels:".bold(),
+                risk.labels.join(", ").cyan()
+            );
+        }
+    }
+
+    if let Some((pr, action)) = &created {
+        status(format!(
+            "\n{} {} {}",
+            "✓".green().bold(),
+            format!("{}:", action).green(),
+            pr.url
+        ));
+    }
 
     // Copy to clipboard if requested
-    if args.copy {
+    if let Some(target) = args.copy {
+        let text = match target {
+            CopyTarget::All => description.to_markdown(),
+            CopyTarget::Title => description.title.clone(),
+            CopyTarget::Body => description.body.clone(),
+        };
         match Clipboard::new() {
             Ok(mut clipboard) => {
-                if clipboard.set_text(&response).is_ok() {
-                    println!("\n{}", "✓ Copied to clipboard!".green());
+                if clipboard.set_text(&text).is_ok() {
+                    status(format!("\n{}", "✓ Copied to clipboard!".green()));
                 } else {
                     eprintln!("{}", "Failed to copy to clipboard.".yellow());
                 }
@@ -88,14 +513,574 @@ pub async fn run(args: PrArgs) -> anyhow::Result<()> {
                 eprintln!("{}", "Clipboard not available.".yellow());
             }
         }
-    } else {
+    } else if !progress_to_stderr && created.is_none() {
         println!("\n{}", "Tip: Use --copy to copy to clipboard.".dimmed());
     }
 
     Ok(())
 }
 
-fn build_pr_prompt(diff: &str, commits: &[String], config: &Config) -> String {
+/// Push the branch if needed and open the pull request, or update the one
+/// already open for the branch. Returns the pull request and a description
+/// of what was done.
+async fn create_pull_request(
+    args: &PrArgs,
+    config: &Config,
+    description: &PrDescription,
+    owners: &[String],
+    base: &str,
+    head: &str,
+    status: &impl Fn(String),
+) -> anyhow::Result<(PullRequest, String)> {
+    let (repo, fork) = forge_repos(&args.base_options())?;
+    let forge = forge::connect(&repo, config)?;
+
+    // Check the reviewers before pushing, so a typo doesn't leave a pushed
+    // branch without a pull request
+    let owners = if args.no_owners { &[] } else { owners };
+    let reviewers = collect_reviewers(forge.as_ref(), &args.reviewers, owners).await?;
+
+    if !args.labels.is_empty() && !forge.supports_labels() {
+        eprintln!(
+            "{}",
+            "Labels are not supported on this forge and will be ignored.".yellow()
+        );
+    }
+
+    if Git::needs_push()? {
+        status(format!(
+            "{} {} {}",
+            "Pushing".dimmed(),
+            head.green(),
+            format!("to {}...", args.remote).dimmed()
+        ));
+        Git::push_branch(&args.remote, head)?;
+    }
+
+    let request = NewPullRequest {
+        title: description.title.clone(),
+        body: description.body.clone(),
+        head: head.to_string(),
+        head_repo: fork.clone(),
+        base: base.to_string(),
+        draft: args.draft,
+        labels: args.labels.clone(),
+        reviewers,
+    };
+
+    if let Some(existing) = forge.find_open_pull_request(head, fork.as_ref()).await? {
+        status(
+            format!("Updating {}...", forge.describe(existing.number))
+                .dimmed()
+                .to_string(),
+        );
+        let pr = forge.update_pull_request(existing.number, &request).await?;
+        let action = format!("Updated {}", forge.describe(pr.number));
+        Ok((pr, action))
+    } else {
+        status("Creating pull request...".dimmed().to_string());
+        let pr = forge.create_pull_request(&request).await?;
+        let action = format!("Created {}", forge.describe(pr.number));
+        Ok((pr, action))
+    }
+}
+
+/// The requested reviewers plus the code owners. Unknown requested reviewers
+/// are an error; unknown code owners (e.g. groups) are skipped with a warning.
+async fn collect_reviewers(
+    forge: &dyn Forge,
+    requested: &[String],
+    owners: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let mut reviewers = requested.to_vec();
+    let unknown = forge.unknown_users(&reviewers).await?;
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown reviewer(s): {}", unknown.join(", "));
+    }
+    if owners.is_empty() {
+        return Ok(reviewers);
+    }
+    if !forge.supports_owner_reviewers() {
+        eprintln!(
+            "{} Code owners can't be requested as reviewers on this forge; use --reviewer with account IDs.",
+            "Warning:".yellow().bold()
+        );
+        return Ok(reviewers);
+    }
+
+    // Forges reject review requests to the pull request's author
+    let author = forge.current_user().await.unwrap_or(None);
+    let candidates: Vec<String> = owner_reviewers(owners, forge.supports_team_reviewers())
+        .into_iter()
+        .filter(|reviewer| {
+            let is_author = author
+                .as_ref()
+                .is_some_and(|author| author.eq_ignore_ascii_case(reviewer));
+            !is_author && !reviewers.contains(reviewer)
+        })
+        .collect();
+    let unknown = forge.unknown_users(&candidates).await?;
+    for owner in &unknown {
+        eprintln!(
+            "{} Code owner {} is not a user on this forge; not requesting a review.",
+            "Warning:".yellow().bold(),
+            owner
+        );
+    }
+    reviewers.extend(
+        candidates
+            .into_iter()
+            .filter(|reviewer| !unknown.contains(reviewer)),
+    );
+    Ok(reviewers)
+}
+
+/// Split the base into the branch name used on the forge and the ref to
+/// diff against. By default that is the branch's remote-tracking branch on
+/// the base remote, fetched first with `--fetch` or when it looks stale.
+pub fn resolve_base(
+    options: &BaseOptions,
+    base: &str,
+    status: &impl Fn(String),
+) -> (String, String) {
+    // An explicit remote-tracking branch such as `upstream/main` is used as is
+    if let Some((remote, branch)) = base.split_once('/') {
+        if Git::has_remote(remote) && Git::has_remote_branch(base) {
+            return (branch.to_string(), base.to_string());
+        }
+    }
+    if options.local_base {
+        return (base.to_string(), base.to_string());
+    }
+
+    let remote = default_base_remote(options);
+    let tracking = format!("{}/{}", remote, base);
+
+    let fetch =
+        options.fetch || (Git::has_remote_branch(&tracking) && confirm_stale_fetch(&tracking));
+    if fetch {
+        status(format!("Fetching {}...", tracking).dimmed().to_string());
+        if let Err(e) = Git::fetch(&remote, base) {
+            eprintln!(
+                "{} Failed to fetch {}: {}",
+                "Warning:".yellow().bold(),
+                tracking,
+                e.to_string().trim()
+            );
+        }
+    }
+
+    if Git::has_remote_branch(&tracking) {
+        (base.to_string(), tracking)
+    } else {
+        eprintln!(
+            "{} No remote-tracking branch {}; comparing against the local {}. Use --fetch to fetch it.",
+            "Warning:".yellow().bold(),
+            tracking,
+            base
+        );
+        (base.to_string(), base.to_string())
+    }
+}
+
+/// The remote holding the base branch unless the base names one. In fork
+/// workflows that is the `upstream` remote.
+fn default_base_remote(options: &BaseOptions) -> String {
+    match options.base_remote {
+        Some(remote) => remote.to_string(),
+        None if Git::has_remote("upstream") => "upstream".to_string(),
+        None => options.remote.to_string(),
+    }
+}
+
+/// The remote holding the base branch, as `resolve_base` picks it
+fn base_remote(options: &BaseOptions) -> String {
+    if let Some(base) = options.base {
+        if let Some((remote, _)) = base.split_once('/') {
+            if Git::has_remote(remote) && Git::has_remote_branch(base) {
+                return remote.to_string();
+            }
+        }
+    }
+    default_base_remote(options)
+}
+
+/// The repository the pull request is opened on (the base remote's) and,
+/// when the branch is pushed to a different repository, that fork
+pub fn forge_repos(options: &BaseOptions) -> anyhow::Result<(RemoteRepo, Option<RemoteRepo>)> {
+    let push_url = Git::remote_url(options.remote)?;
+    let base_remote = base_remote(options);
+    let base_url = if base_remote == options.remote {
+        push_url.clone()
+    } else {
+        Git::remote_url(&base_remote)?
+    };
+    Ok(pull_request_repos(&push_url, &base_url)?)
+}
+
+/// Parse the push and base remote URLs into the target repository and the fork, if any
+fn pull_request_repos(
+    push_url: &str,
+    base_url: &str,
+) -> Result<(RemoteRepo, Option<RemoteRepo>), forge::ForgeError> {
+    let push = RemoteRepo::parse(push_url)?;
+    let base = RemoteRepo::parse(base_url)?;
+    let same = push.host.eq_ignore_ascii_case(&base.host)
+        && push.path().eq_ignore_ascii_case(&base.path());
+    Ok(if same {
+        (base, None)
+    } else {
+        (base, Some(push))
+    })
+}
+
+/// Warn when `tracking` was last fetched long ago, and ask whether to fetch
+/// it now when running in a terminal
+fn confirm_stale_fetch(tracking: &str) -> bool {
+    let Some(age) = Git::last_fetched(tracking)
+        .and_then(|time| SystemTime::now().duration_since(time).ok())
+        .filter(|age| *age > STALE_FETCH_AGE)
+    else {
+        return false;
+    };
+
+    let message = format!("{} was last fetched {} ago.", tracking, describe_age(age));
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "{} {} Use --fetch to update it.",
+            "Warning:".yellow().bold(),
+            message
+        );
+        return false;
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("{} Fetch it now?", message))
+        .default(true)
+        .interact()
+        .unwrap_or(false)
+}
+
+fn describe_age(age: Duration) -> String {
+    let hours = age.as_secs() / 3600;
+    match hours {
+        0..=47 => format!("{} hours", hours),
+        _ => format!("{} days", hours / 24),
+    }
+}
+
+/// Code owners of the files changed since `base`, with the CODEOWNERS path
+fn code_ownership(base: &str) -> Option<(PathBuf, Ownership)> {
+    let root = Git::repo_root().ok()?;
+    let codeowners = CodeOwners::find(&root)?;
+    let files = Git::get_changed_files(base).ok()?;
+    let ownership = codeowners.ownership(&files);
+    Some((codeowners.path, ownership))
+}
+
+fn print_ownership(path: &Path, ownership: &Ownership) {
+    println!(
+        "\n{} {}",
+        "Code owners".bold(),
+        format!("({})", path.display()).dimmed()
+    );
+    if ownership.owners.is_empty() {
+        println!("  {}", "(none)".dimmed());
+    } else {
+        println!("  {}", ownership.owners.join(", ").cyan());
+    }
+    if !ownership.unowned.is_empty() {
+        println!("{}", "No owner:".yellow());
+        for path in &ownership.unowned {
+            println!("  {}", path);
+        }
+    }
+}
+
+/// Put the computed risk summary under the "## Risk" heading the model was
+/// asked to write, or add the heading if it's missing
+fn add_risk_summary(body: &str, risk: &RiskReport) -> String {
+    let summary = risk.summary();
+    let mut lines: Vec<&str> = body.lines().collect();
+    match lines
+        .iter()
+        .position(|line| line.trim().eq_ignore_ascii_case("## Risk"))
+    {
+        Some(heading) => {
+            lines.insert(heading + 1, "");
+            lines.insert(heading + 2, &summary);
+            if heading + 3 < lines.len() {
+                lines.insert(heading + 3, "");
+            }
+            lines.join("\n")
+        }
+        None if body.trim().is_empty() => format!("## Risk\n\n{}", summary),
+        None => format!("{}\n\n## Risk\n\n{}", body.trim_end(), summary),
+    }
+}
+
+/// Turn CODEOWNERS entries into reviewer names for the forge. Email owners
+/// are skipped, as are teams where the forge can't request team reviews.
+fn owner_reviewers(owners: &[String], teams: bool) -> Vec<String> {
+    owners
+        .iter()
+        .filter_map(|owner| owner.strip_prefix('@'))
+        .filter(|name| teams || !name.contains('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Find the open pull request for `head` on the forge hosting the base remote
+async fn find_pull_request(
+    args: &PrArgs,
+    config: &Config,
+    head: &str,
+) -> anyhow::Result<(Box<dyn Forge>, PullRequest)> {
+    let (repo, fork) = forge_repos(&args.base_options())?;
+    let forge = forge::connect(&repo, config)?;
+    match forge.find_open_pull_request(head, fork.as_ref()).await? {
+        Some(pr) => Ok((forge, pr)),
+        None => anyhow::bail!(
+            "No open pull request found for branch '{}'. Use --create to open one.",
+            head
+        ),
+    }
+}
+
+/// Show the changes to the body of `existing` and apply them after confirmation
+async fn update_pull_request(
+    args: &PrArgs,
+    forge: &dyn Forge,
+    existing: &PullRequest,
+    description: &PrDescription,
+    head: &str,
+    status: &impl Fn(String),
+) -> anyhow::Result<(PullRequest, String)> {
+    let name = forge.describe(existing.number);
+    if description.body.trim() == existing.body.trim() {
+        return Ok((existing.clone(), format!("Already up to date: {}", name)));
+    }
+
+    if !args.output.is_json() {
+        println!("\n{} {}", "Changes to".bold(), name.bold());
+        println!("{}", "━".repeat(60).dimmed());
+        for line in diff_lines(&existing.body, &description.body) {
+            match line {
+                DiffLine::Same(text) => println!("  {}", text.dimmed()),
+                DiffLine::Removed(text) => println!("{}", format!("- {}", text).red()),
+                DiffLine::Added(text) => println!("{}", format!("+ {}", text).green()),
+            }
+        }
+        println!("{}", "━".repeat(60).dimmed());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to apply the update.");
+        }
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Update {}?", name))
+            .default(true)
+            .interact()?;
+        if !confirmed {
+            eprintln!("{}", "Aborted.".yellow());
+            std::process::exit(1);
+        }
+    }
+
+    status(format!("Updating {}...", name).dimmed().to_string());
+    let request = NewPullRequest {
+        title: description.title.clone(),
+        body: description.body.clone(),
+        head: head.to_string(),
+        head_repo: None,
+        base: existing.base.clone(),
+        draft: false,
+        labels: Vec::new(),
+        reviewers: Vec::new(),
+    };
+    let pr = forge.update_pull_request(existing.number, &request).await?;
+    Ok((pr, format!("Updated {}", name)))
+}
+
+/// Append the sentinel-marked sections of `old_body` to the regenerated body
+fn merge_kept_sections(old_body: &str, new_body: &str) -> String {
+    let mut parts = vec![new_body.trim()];
+    parts.extend(kept_sections(old_body));
+    parts.retain(|part| !part.is_empty());
+    parts.join("\n\n")
+}
+
+/// Sections between the keep sentinels, markers included. An unterminated
+/// section runs to the end of the body.
+fn kept_sections(body: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find(KEEP_START) {
+        let section = &rest[start..];
+        match section.find(KEEP_END) {
+            Some(end) => {
+                let end = end + KEEP_END.len();
+                sections.push(&section[..end]);
+                rest = &section[end..];
+            }
+            None => {
+                sections.push(section.trim_end());
+                break;
+            }
+        }
+    }
+    sections
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff of two texts based on their longest common subsequence
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+    lines
+}
+
+/// Read the template given with `--template`, or find the repository's
+/// templates and let the user pick one when there are several
+fn load_template(args: &PrArgs) -> anyhow::Result<Option<(PathBuf, String)>> {
+    if args.no_template {
+        return Ok(None);
+    }
+    if let Some(ref path) = args.template {
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        return Ok(Some((path.clone(), text)));
+    }
+
+    let root = Git::repo_root()?;
+    let templates = find_templates(&root);
+    let path = match templates.len() {
+        0 => return Ok(None),
+        1 => templates[0].clone(),
+        _ if !std::io::stdin().is_terminal() => templates[0].clone(),
+        _ => {
+            let mut items: Vec<String> = templates
+                .iter()
+                .map(|path| {
+                    path.strip_prefix(&root)
+                        .unwrap_or(path)
+                        .display()
+                        .to_string()
+                })
+                .collect();
+            items.push("(no template)".to_string());
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a pull request template")
+                .items(&items)
+                .default(0)
+                .interact()?;
+            match templates.get(selection) {
+                Some(path) => path.clone(),
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let text = fs::read_to_string(&path)?;
+    let display = path.strip_prefix(&root).unwrap_or(&path).to_path_buf();
+    Ok(Some((display, text)))
+}
+
+/// Find pull request templates in the standard locations under `root`
+fn find_templates(root: &Path) -> Vec<PathBuf> {
+    let mut templates = Vec::new();
+    for dir in TEMPLATE_DIRS {
+        for path in sorted_entries(&root.join(dir)) {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+            if name == "pull_request_template.md" && path.is_file() {
+                templates.push(path);
+            } else if name == "pull_request_template" && path.is_dir() {
+                templates.extend(markdown_files(&path));
+            }
+        }
+    }
+    templates.extend(markdown_files(&root.join(GITLAB_TEMPLATE_DIR)));
+    templates
+}
+
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    sorted_entries(dir)
+        .into_iter()
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect()
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    paths.sort();
+    paths
+}
+
+/// Markdown headings of the template that do not appear in the body
+fn missing_headings<'a>(template: &'a str, body: &str) -> Vec<&'a str> {
+    let body_lines: Vec<&str> = body.lines().map(str::trim).collect();
+    template
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .filter(|heading| !body_lines.contains(heading))
+        .collect()
+}
+
+fn build_pr_prompt(
+    diff: &str,
+    commits: &[String],
+    config: &Config,
+    template: Option<&str>,
+    risk: Option<&RiskReport>,
+) -> String {
     let language_instruction = match config.options.language {
         Language::Ko => "Write the PR description in Korean.",
         Language::En => "Write the PR description in English.",
@@ -114,19 +1099,46 @@ fn build_pr_prompt(diff: &str, commits: &[String], config: &Config) -> String {
         String::new()
     };
 
+    let body_instructions = match template {
+        Some(template) => format!(
+            r#"- Write the body by filling in the repository's pull request template below.
+- Keep every heading, checkbox and list item of the template exactly as written, in the same order.
+- Write the content for each section under its heading; replace placeholder text and HTML comments with real content.
+- Check a box ("- [x]") only when the changes clearly satisfy it; leave the others unchecked.
+- If a section does not apply, write "N/A" under its heading instead of removing it.
+
+Pull request template:
+```
+{}
+```"#,
+            template.trim()
+        ),
+        None => "- Include a summary section explaining what this PR does.\n- Include a list of key changes.".to_string(),
+    };
+
+    let (risk_instruction, risk_section) = match risk {
+        Some(risk) => (
+            "\n- End the body with a \"## Risk\" section: one to three short bullet notes for reviewers, based only on the risk facts below.",
+            format!("Risk facts:\n{}", risk.facts()),
+        ),
+        None => ("", String::new()),
+    };
+
     format!(
         r#"You are a helpful assistant that generates Pull Request descriptions.
 
 Instructions:
 - {language_instruction}
 - Generate a clear, well-structured PR description.
-- Include a concise title (first line, without any prefix like "Title:").
-- Include a summary section explaining what this PR does.
-- Include a list of key changes.
+- Put a concise title (under 72 characters) on the first line, without any prefix like "Title:" and without markdown heading markers.
+- Leave a blank line after the title, then write the body.
+{body_instructions}{risk_instruction}
 - Keep it professional and informative.
 
 {commits_section}
 
+{risk_section}
+
 Git diff (truncated if too long):
 ```
 {diff}
@@ -134,15 +1146,273 @@ Git diff (truncated if too long):
 
 Generate the PR title and description:"#,
         language_instruction = language_instruction,
+        body_instructions = body_instructions,
+        risk_instruction = risk_instruction,
         commits_section = commits_section,
+        risk_section = risk_section,
         diff = truncate_diff(diff, 4000)
     )
 }
 
-fn truncate_diff(diff: &str, max_chars: usize) -> &str {
-    if diff.len() <= max_chars {
-        diff
-    } else {
-        &diff[..max_chars]
+/// Remove a code fence wrapping the whole response
+pub fn strip_code_fence(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("```") else {
+        return text;
+    };
+    let Some(inner) = rest.strip_suffix("```") else {
+        return text;
+    };
+    // Drop the info string, e.g. ```markdown
+    match inner.split_once('\n') {
+        Some((_, content)) => content.trim(),
+        None => inner.trim(),
+    }
+}
+
+fn clean_title(line: &str) -> String {
+    let mut title = line.trim().trim_start_matches('#').trim();
+    for prefix in [
+        "**Title:**",
+        "**Title**:",
+        "Title:",
+        "title:",
+        "TITLE:",
+        "PR Title:",
+    ] {
+        if let Some(rest) = title.strip_prefix(prefix) {
+            title = rest.trim();
+        }
+    }
+    let title = title
+        .trim_matches('*')
+        .trim_matches('`')
+        .trim_matches('"')
+        .trim();
+    title.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::GitLabClient;
+    use crate::mock::MockServer;
+
+    #[test]
+    fn parse_splits_title_and_body() {
+        let desc = PrDescription::parse("\nAdd login\n\n## Summary\nAdds login.\n");
+        assert_eq!(desc.title, "Add login");
+        assert_eq!(desc.body, "## Summary\nAdds login.");
+    }
+
+    #[test]
+    fn parse_title_only() {
+        let desc = PrDescription::parse("Add login");
+        assert_eq!(desc.title, "Add login");
+        assert_eq!(desc.body, "");
+    }
+
+    #[test]
+    fn parse_strips_title_prefix_and_heading() {
+        assert_eq!(PrDescription::parse("Title: Add login").title, "Add login");
+        assert_eq!(PrDescription::parse("# Add login").title, "Add login");
+        assert_eq!(
+            PrDescription::parse("## **Title:** Add login").title,
+            "Add login"
+        );
+        assert_eq!(PrDescription::parse("\"Add login\"").title, "Add login");
+    }
+
+    #[test]
+    fn parse_strips_code_fence() {
+        let desc = PrDescription::parse("```markdown\nAdd login\n\nBody text\n```");
+        assert_eq!(desc.title, "Add login");
+        assert_eq!(desc.body, "Body text");
+    }
+
+    #[test]
+    fn validate_reports_long_title_and_empty_body() {
+        let desc = PrDescription {
+            title: "x".repeat(80),
+            body: String::new(),
+        };
+        let warnings = desc.validate();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("80 characters"));
+
+        let desc = PrDescription {
+            title: "Add login".to_string(),
+            body: "Adds login.".to_string(),
+        };
+        assert!(desc.validate().is_empty());
+    }
+
+    #[test]
+    fn find_templates_in_standard_locations() {
+        let root = std::env::temp_dir().join(format!("git-ai-templates-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".github/PULL_REQUEST_TEMPLATE")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join(".github/PULL_REQUEST_TEMPLATE/feature.md"), "").unwrap();
+        fs::write(root.join(".github/PULL_REQUEST_TEMPLATE/bugfix.md"), "").unwrap();
+        fs::write(root.join(".github/PULL_REQUEST_TEMPLATE/notes.txt"), "").unwrap();
+        fs::write(root.join("docs/pull_request_template.md"), "").unwrap();
+
+        let found: Vec<PathBuf> = find_templates(&root)
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap().to_path_buf())
+            .collect();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            found,
+            vec![
+                PathBuf::from(".github/PULL_REQUEST_TEMPLATE/bugfix.md"),
+                PathBuf::from(".github/PULL_REQUEST_TEMPLATE/feature.md"),
+                PathBuf::from("docs/pull_request_template.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_headings_reports_dropped_sections() {
+        let template = "## Summary\n<!-- what -->\n\n## Checklist\n- [ ] Tests\n";
+        let body = "## Summary\nAdds login.\n\n- [x] Tests";
+        assert_eq!(missing_headings(template, body), vec!["## Checklist"]);
+    }
+
+    #[test]
+    fn prompt_includes_template() {
+        let config = Config::default();
+        let prompt = build_pr_prompt(
+            "diff",
+            &[],
+            &config,
+            Some("## Checklist\n- [ ] Tests"),
+            None,
+        );
+        assert!(prompt.contains("- [ ] Tests"));
+        assert!(!prompt.contains("Include a list of key changes."));
+    }
+
+    #[test]
+    fn add_risk_summary_under_heading() {
+        let risk = RiskReport::assess(
+            &[crate::git::FileStat {
+                path: "src/lib.rs".to_string(),
+                additions: 3,
+                deletions: 1,
+            }],
+            &Config::default().risk,
+        );
+        let body = add_risk_summary("## Summary\nText\n\n## Risk\n- Low risk.", &risk);
+        assert_eq!(
+            body,
+            "## Summary\nText\n\n## Risk\n\n**Size:** XS (1 file, +3 −1) · **Tests:** none changed\n\n- Low risk."
+        );
+        assert!(add_risk_summary("## Summary", &risk)
+            .ends_with("## Risk\n\n**Size:** XS (1 file, +3 −1) · **Tests:** none changed"));
+    }
+
+    #[test]
+    fn describe_age_in_hours_then_days() {
+        assert_eq!(describe_age(Duration::from_secs(30 * 3600)), "30 hours");
+        assert_eq!(describe_age(Duration::from_secs(72 * 3600)), "3 days");
+    }
+
+    #[test]
+    fn owner_reviewers_skips_emails_and_unsupported_teams() {
+        let owners = vec![
+            "@alice".to_string(),
+            "@org/core".to_string(),
+            "bob@example.com".to_string(),
+        ];
+        assert_eq!(owner_reviewers(&owners, true), vec!["alice", "org/core"]);
+        assert_eq!(owner_reviewers(&owners, false), vec!["alice"]);
+    }
+
+    #[test]
+    fn pull_request_repos_with_fork_and_upstream_remotes() {
+        let (repo, fork) = pull_request_repos(
+            "git@github.com:alice/app.git",
+            "https://github.com/octo/app.git",
+        )
+        .unwrap();
+        assert_eq!(repo.path(), "octo/app");
+        assert_eq!(fork.unwrap().path(), "alice/app");
+
+        let (repo, fork) =
+            pull_request_repos("git@github.com:octo/app.git", "https://github.com/Octo/app")
+                .unwrap();
+        assert_eq!(repo.path(), "Octo/app");
+        assert_eq!(fork, None);
+    }
+
+    #[tokio::test]
+    async fn collect_reviewers_skips_unknown_owners() {
+        let server = MockServer::start(vec![
+            (200, r#"[{"id": 2}]"#),
+            (200, r#"{"username": "carol"}"#),
+            (200, r#"[{"id": 1}]"#),
+            (200, "[]"),
+        ]);
+        let remote = RemoteRepo::parse("git@gitlab.example.com:group/app.git").unwrap();
+        let forge = GitLabClient::new(&server.url, "glpat".to_string(), &remote);
+
+        let owners = vec![
+            "@alice".to_string(),
+            "@backend".to_string(),
+            "@carol".to_string(),
+        ];
+        let reviewers = collect_reviewers(&forge, &["@bob".to_string()], &owners)
+            .await
+            .unwrap();
+        assert_eq!(reviewers, vec!["@bob", "alice"]);
+    }
+
+    #[tokio::test]
+    async fn collect_reviewers_rejects_unknown_requested_reviewers() {
+        let server = MockServer::start(vec![(200, "[]")]);
+        let remote = RemoteRepo::parse("git@gitlab.example.com:group/app.git").unwrap();
+        let forge = GitLabClient::new(&server.url, "glpat".to_string(), &remote);
+
+        let error = collect_reviewers(&forge, &["@bob".to_string()], &["@alice".to_string()])
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Unknown reviewer(s): @bob");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn merge_keeps_marked_sections() {
+        let old = "## Summary\nOld.\n\n<!-- git-ai:keep -->\n## QA\nTested on staging.\n<!-- /git-ai:keep -->\n\nTrailing text";
+        let merged = merge_kept_sections(old, "## Summary\nNew.\n");
+        assert_eq!(
+            merged,
+            "## Summary\nNew.\n\n<!-- git-ai:keep -->\n## QA\nTested on staging.\n<!-- /git-ai:keep -->"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_unterminated_section_to_end() {
+        let old = "Old.\n<!-- git-ai:keep -->\nNotes\n";
+        assert_eq!(
+            merge_kept_sections(old, "New."),
+            "New.\n\n<!-- git-ai:keep -->\nNotes"
+        );
+        assert_eq!(merge_kept_sections("Old.", "New."), "New.");
+    }
+
+    #[test]
+    fn diff_lines_marks_changes() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
     }
 }
@@ -0,0 +1,269 @@
+use super::changelog::{clean_sections, commits_context};
+use super::GenerationArgs;
+use crate::changelog::{self, Conventional};
+use crate::config::{Config, Language};
+use crate::git::Git;
+use crate::llm::{LlmClient, Usage};
+use crate::output::{print_json, OutputFormat};
+use crate::version::{Bump, Version};
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::time::Instant;
+
+#[derive(Args)]
+pub struct ReleaseArgs {
+    /// Override the suggested version bump
+    #[arg(long, value_enum, conflicts_with = "release")]
+    pub bump: Option<Bump>,
+
+    /// Use this version instead of the suggested one
+    #[arg(long, value_name = "VERSION")]
+    pub release: Option<String>,
+
+    /// Include docs, tests, CI and chores in the notes
+    #[arg(long)]
+    pub all: bool,
+
+    /// Create an annotated tag with the release notes as its message
+    #[arg(long)]
+    pub tag: bool,
+
+    /// Create the tag without confirmation
+    #[arg(long, short = 'y', requires = "tag")]
+    pub yes: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// JSON document printed by `release --output json`
+#[derive(Serialize)]
+struct ReleaseOutput<'a> {
+    previous_tag: Option<&'a str>,
+    version: String,
+    tag: &'a str,
+    bump: Option<Bump>,
+    commits: usize,
+    notes: &'a str,
+    tagged: bool,
+    provider: &'a str,
+    model: &'a str,
+    usage: Option<Usage>,
+    duration_ms: u128,
+}
+
+pub async fn run(args: ReleaseArgs) -> anyhow::Result<()> {
+    let output = args.output;
+
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let previous = latest_version_tag(&Git::tags("HEAD").unwrap_or_default());
+    let previous_tag = previous.as_ref().map(|(tag, _)| tag.as_str());
+    let commits = match Git::get_commit_log_without_merges(previous_tag, "HEAD") {
+        Ok(commits) if !commits.is_empty() => commits,
+        Ok(_) => {
+            let since = previous_tag.unwrap_or("the start");
+            eprintln!("{}", format!("No commits since {}.", since).yellow());
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let conventional: Vec<Conventional> = commits.iter().map(Conventional::parse).collect();
+    let current = previous
+        .as_ref()
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default();
+    let (next, bump) = match args.release {
+        Some(ref release) => match Version::parse(release) {
+            Some(version) => (version, None),
+            None => {
+                eprintln!(
+                    "{} '{}' is not a semantic version (MAJOR.MINOR.PATCH).",
+                    "Error:".red().bold(),
+                    release
+                );
+                std::process::exit(1);
+            }
+        },
+        None => {
+            let bump = args
+                .bump
+                .unwrap_or_else(|| Bump::from_commits(&conventional, &current));
+            (current.bump(bump), Some(bump))
+        }
+    };
+    // Keep the tag naming of the previous release; new projects get a "v"
+    let prefix = match previous_tag {
+        Some(tag) if !tag.starts_with('v') => "",
+        _ => "v",
+    };
+    let tag = format!("{}{}", prefix, next);
+
+    output.status(format!(
+        "{} {} → {} {}",
+        "Version:".dimmed(),
+        previous_tag.unwrap_or("(none)").cyan(),
+        tag.green().bold(),
+        bump.map(|b| format!("({}, {} commit(s))", b, commits.len()))
+            .unwrap_or_else(|| format!("({} commit(s))", commits.len()))
+            .dimmed()
+    ));
+
+    output.status("Writing release notes...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let groups = changelog::group(&commits, args.all);
+    let started = Instant::now();
+    let completion = client
+        .generate(&build_release_prompt(
+            &tag,
+            &changelog::render_groups(&groups),
+            &commits_context(&groups),
+            &config,
+        ))
+        .await?;
+    let duration = started.elapsed();
+    let notes = clean_sections(&completion.text);
+
+    if !output.is_json() {
+        println!("\n{}", "━".repeat(60).dimmed());
+        println!("{}\n", tag.bold());
+        println!("{}", notes);
+        println!("{}", "━".repeat(60).dimmed());
+    }
+
+    let tagged = args.tag && create_tag(&args, &tag, &notes)?;
+
+    if output.is_json() {
+        print_json(&ReleaseOutput {
+            previous_tag,
+            version: next.to_string(),
+            tag: &tag,
+            bump,
+            commits: commits.len(),
+            notes: &notes,
+            tagged,
+            provider: client.provider_name(),
+            model: client.model(),
+            usage: completion.usage,
+            duration_ms: duration.as_millis(),
+        })?;
+    } else if tagged {
+        println!(
+            "\n{} {} {}",
+            "✓".green().bold(),
+            "Created tag".green(),
+            tag.bold()
+        );
+        println!(
+            "  {}",
+            format!("Push it with: git push origin {}", tag).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Create the annotated tag after confirmation. Returns false if declined.
+fn create_tag(args: &ReleaseArgs, tag: &str, notes: &str) -> anyhow::Result<bool> {
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to create the tag.");
+        }
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Create tag {}?", tag))
+            .default(true)
+            .interact()?;
+        if !confirmed {
+            eprintln!("{}", "Tag not created.".yellow());
+            return Ok(false);
+        }
+    }
+
+    Git::create_tag(tag, &format!("Release {}\n\n{}\n", tag, notes))?;
+    Ok(true)
+}
+
+/// The highest semantic version among `tags`, with its tag name
+fn latest_version_tag(tags: &[String]) -> Option<(String, Version)> {
+    tags.iter()
+        .filter_map(|tag| Version::parse(tag).map(|version| (tag.clone(), version)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+fn build_release_prompt(tag: &str, draft: &str, context: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the release notes in Korean.",
+        Language::En => "Write the release notes in English.",
+    };
+
+    let context = if context.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n\nCommit messages with details (truncated if too long):\n```\n{}\n```",
+            super::commit::truncate_diff(context, 6000)
+        )
+    };
+
+    format!(
+        r#"You are writing the release notes for version {tag} of a project, for its users.
+Below is a draft with the commits of this release grouped into sections.
+
+Instructions:
+- {language_instruction}
+- Start with a ### Highlights section: two to four bullets on the changes users care about most.
+- Then keep the draft's sections (### Added, ### Fixed, ...) in their order, with each entry rewritten so that a user understands what changed for them.
+- Call out breaking changes and what users need to do about them.
+- Merge entries that describe the same change and drop entries with no effect on users.
+- Respond with ONLY the sections as markdown, without a title or any other text.
+
+Draft:
+```
+{draft}
+```{context}"#,
+        tag = tag,
+        language_instruction = language_instruction,
+        draft = draft,
+        context = context
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_version_tag_ignores_other_tags() {
+        let tags: Vec<String> = ["v1.9.0", "v1.10.0-rc.1", "v1.10.0", "nightly", "v1.2.0"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (tag, version) = latest_version_tag(&tags).unwrap();
+        assert_eq!(tag, "v1.10.0");
+        assert_eq!(version.to_string(), "1.10.0");
+        assert!(latest_version_tag(&["nightly".to_string()]).is_none());
+    }
+}
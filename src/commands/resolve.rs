@@ -0,0 +1,369 @@
+use super::commit::truncate_diff;
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::conflict::{self, Conflict};
+use crate::git::Git;
+use crate::llm::LlmClient;
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Editor, Select};
+use std::io::IsTerminal;
+
+/// Lines around a conflict shown to the model
+const CONTEXT_LINES: usize = 10;
+
+/// Unchanged lines used to find a conflict's base version in the base file
+const ANCHOR_LINES: usize = 3;
+
+#[derive(Args)]
+pub struct ResolveArgs {
+    /// Conflicted files to resolve (default: all)
+    pub paths: Vec<String>,
+
+    /// Accept every proposed resolution without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the explanations and proposals, don't change any file
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// What the user chose for a proposed resolution
+enum Choice {
+    Accept(String),
+    Skip,
+}
+
+pub async fn run(args: ResolveArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    // Conflicted paths are relative to the repository root, the given ones
+    // to the current directory
+    let prefix = Git::show_prefix()?;
+    let paths: Vec<String> = args
+        .paths
+        .iter()
+        .map(|p| root_relative(&prefix, p))
+        .collect();
+    std::env::set_current_dir(Git::repo_root()?)?;
+    let mut files = Git::conflicted_files()?;
+    if !paths.is_empty() {
+        files.retain(|file| paths.iter().any(|p| p == file));
+    }
+    if files.is_empty() {
+        println!("{}", "No conflicted files.".green());
+        return Ok(());
+    }
+    if !args.yes && !args.dry_run && !std::io::stdin().is_terminal() {
+        anyhow::bail!("Not a terminal; re-run with --yes to accept the proposed resolutions");
+    }
+
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let mut remaining = 0;
+    for file in &files {
+        remaining += resolve_file(&client, file, &args, &config).await?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+    if remaining == 0 {
+        println!(
+            "\n{} {}",
+            "✓".green().bold(),
+            "All conflicts resolved. Review the result, then continue the merge or rebase.".green()
+        );
+    } else {
+        println!(
+            "\n{}",
+            format!("{} conflict(s) left to resolve by hand.", remaining).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// A path given from the current directory, relative to the repository root
+fn root_relative(prefix: &str, path: &str) -> String {
+    let mut parts: Vec<&str> = prefix.split('/').filter(|p| !p.is_empty()).collect();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Resolve the conflicts of one file and return how many are left
+async fn resolve_file(
+    client: &LlmClient,
+    file: &str,
+    args: &ResolveArgs,
+    config: &Config,
+) -> anyhow::Result<usize> {
+    // Deleted on one side, or not text: nothing to merge line by line
+    let content = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!(
+                "\n{} Skipping {}: {}",
+                "Warning:".yellow().bold(),
+                file.bold(),
+                e
+            );
+            return Ok(1);
+        }
+    };
+    let conflicts = conflict::parse(&content);
+    println!(
+        "\n{} {}",
+        file.bold(),
+        format!("({} conflict(s))", conflicts.len()).dimmed()
+    );
+    if conflicts.is_empty() {
+        println!(
+            "  {}",
+            "No conflict markers left; stage the file with 'git add' when it's ready.".dimmed()
+        );
+        return Ok(0);
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let base_file = Git::show_stage(1, file);
+    let mut resolutions = vec![None; conflicts.len()];
+
+    for (i, c) in conflicts.iter().enumerate() {
+        let before = &lines[c.start.saturating_sub(CONTEXT_LINES)..c.start];
+        let after = &lines[c.end.min(lines.len())..(c.end + CONTEXT_LINES).min(lines.len())];
+        let base = c.base.clone().or_else(|| {
+            let anchor_before = &before[before.len().saturating_sub(ANCHOR_LINES)..];
+            let anchor_after = &after[..after.len().min(ANCHOR_LINES)];
+            conflict::base_hunk(base_file.as_deref()?, anchor_before, anchor_after)
+        });
+
+        println!(
+            "\n{} {}",
+            format!("Conflict {}/{}", i + 1, conflicts.len())
+                .cyan()
+                .bold(),
+            format!("(line {})", c.start + 1).dimmed()
+        );
+        print_side(&format!("ours ({})", c.ours_label), &c.ours, |s| {
+            s.red().to_string()
+        });
+        match base {
+            Some(ref base) => print_side("base", base, |s| s.dimmed().to_string()),
+            None => println!("  {}", "base: unknown".dimmed()),
+        }
+        print_side(&format!("theirs ({})", c.theirs_label), &c.theirs, |s| {
+            s.green().to_string()
+        });
+
+        let prompt = build_resolve_prompt(file, c, base.as_deref(), before, after, config);
+        let completion = client.generate(&prompt).await?;
+        let Some((explanation, resolution)) = parse_proposal(&completion.text) else {
+            eprintln!(
+                "{} No resolution in the response; skipping this conflict.",
+                "Warning:".yellow().bold()
+            );
+            continue;
+        };
+
+        println!("\n  {}", explanation.trim());
+        print_side("proposed resolution", &resolution, |s| s.cyan().to_string());
+
+        if args.dry_run {
+            continue;
+        }
+        let choice = if args.yes {
+            Choice::Accept(resolution)
+        } else {
+            choose(resolution)?
+        };
+        if let Choice::Accept(resolution) = choice {
+            resolutions[i] = Some(resolution);
+        }
+    }
+
+    let resolved = resolutions.iter().filter(|r| r.is_some()).count();
+    let remaining = conflicts.len() - resolved;
+    if args.dry_run || resolved == 0 {
+        return Ok(remaining);
+    }
+
+    std::fs::write(file, conflict::resolve(&content, &conflicts, &resolutions))?;
+    if remaining == 0 {
+        Git::stage_file(file)?;
+        println!(
+            "\n{} {} {}",
+            "✓".green().bold(),
+            "Resolved and staged".green(),
+            file
+        );
+    } else {
+        println!(
+            "\n{}",
+            format!(
+                "Wrote {} resolution(s) to {}; {} conflict(s) left.",
+                resolved, file, remaining
+            )
+            .yellow()
+        );
+    }
+    Ok(remaining)
+}
+
+fn choose(resolution: String) -> anyhow::Result<Choice> {
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Use this resolution?")
+        .items(&["Accept", "Edit", "Skip"])
+        .default(0)
+        .interact()?;
+    match selection {
+        0 => Ok(Choice::Accept(resolution)),
+        1 => {
+            let edited = Editor::new()
+                .executable(Git::editor()?)
+                .trim_newlines(false)
+                .edit(&resolution)?;
+            Ok(edited.map_or(Choice::Skip, Choice::Accept))
+        }
+        _ => Ok(Choice::Skip),
+    }
+}
+
+fn print_side(label: &str, text: &str, paint: impl Fn(&str) -> String) {
+    println!("  {}", format!("{}:", label).bold());
+    if text.is_empty() {
+        println!("    {}", "(empty)".dimmed());
+    }
+    for line in text.lines() {
+        println!("    {}", paint(line));
+    }
+}
+
+fn build_resolve_prompt(
+    file: &str,
+    conflict: &Conflict,
+    base: Option<&str>,
+    before: &[&str],
+    after: &[&str],
+    config: &Config,
+) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the explanation in Korean.",
+        Language::En => "Write the explanation in English.",
+    };
+    let base = match base {
+        Some(base) => format!("```\n{}```", truncate_diff(base, 4000)),
+        None => "(not available)".to_string(),
+    };
+
+    format!(
+        r#"Help resolve a merge conflict in {file}.
+
+Instructions:
+- {language_instruction}
+- First explain in 1-3 sentences what each side changed compared to the base and how to combine them.
+- Then output the resolved code in a single fenced code block, exactly as it should replace the conflict in the file, without conflict markers.
+- Keep the intent of both sides where possible. If one side should win, say why.
+
+Code before the conflict:
+```
+{before}
+```
+
+Ours ({ours_label}):
+```
+{ours}```
+
+Base (common ancestor):
+{base}
+
+Theirs ({theirs_label}):
+```
+{theirs}```
+
+Code after the conflict:
+```
+{after}
+```"#,
+        file = file,
+        language_instruction = language_instruction,
+        before = before.join("\n"),
+        ours_label = conflict.ours_label,
+        ours = truncate_diff(&conflict.ours, 4000),
+        base = base,
+        theirs_label = conflict.theirs_label,
+        theirs = truncate_diff(&conflict.theirs, 4000),
+        after = after.join("\n")
+    )
+}
+
+/// Split the response into the explanation and the code of its fenced block
+fn parse_proposal(response: &str) -> Option<(String, String)> {
+    let lines: Vec<&str> = response.lines().collect();
+    let open = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("```"))?;
+    let close = lines.iter().rposition(|l| l.trim() == "```")?;
+    if close <= open {
+        return None;
+    }
+
+    let explanation = lines[..open].join("\n");
+    let mut resolution = lines[open + 1..close].join("\n");
+    if !resolution.is_empty() {
+        resolution.push('\n');
+    }
+    Some((explanation, resolution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_relative_prefixes_paths_from_the_current_directory() {
+        assert_eq!(root_relative("", "src/main.rs"), "src/main.rs");
+        assert_eq!(root_relative("src/", "main.rs"), "src/main.rs");
+        assert_eq!(root_relative("src/commands/", "../git.rs"), "src/git.rs");
+        assert_eq!(root_relative("src/", "./commands/"), "src/commands");
+    }
+
+    #[test]
+    fn parse_proposal_takes_explanation_and_code() {
+        let response =
+            "Ours raised the limit, theirs added a delay. Keep both.\n\n```rust\n    let retries = 5;\n    let delay = 10;\n```\n";
+        let (explanation, resolution) = parse_proposal(response).unwrap();
+        assert_eq!(
+            explanation.trim(),
+            "Ours raised the limit, theirs added a delay. Keep both."
+        );
+        assert_eq!(resolution, "    let retries = 5;\n    let delay = 10;\n");
+
+        let (_, empty) = parse_proposal("Drop both.\n```\n```").unwrap();
+        assert_eq!(empty, "");
+        assert_eq!(parse_proposal("no code here"), None);
+    }
+}
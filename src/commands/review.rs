@@ -0,0 +1,470 @@
+use super::commit::truncate_diff;
+use super::pr::{forge_repos, resolve_base, BaseOptions};
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::forge::{self, PullRequest, ReviewComment};
+use crate::git::Git;
+use crate::llm::{LlmClient, Usage};
+use crate::output::{print_json, OutputFormat};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// Review the staged changes instead of the branch
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Base branch to compare against (default: main or master)
+    #[arg(long, short = 'b', conflicts_with = "staged")]
+    pub base: Option<String>,
+
+    /// Remote holding the base branch (default: upstream if it exists, else --remote)
+    #[arg(long, value_name = "REMOTE", conflicts_with = "staged")]
+    pub base_remote: Option<String>,
+
+    /// Fetch the base branch before comparing
+    #[arg(long, conflicts_with_all = ["staged", "local_base"])]
+    pub fetch: bool,
+
+    /// Compare against the local base branch instead of its remote-tracking branch
+    #[arg(long, conflicts_with = "staged")]
+    pub local_base: bool,
+
+    /// Post the findings as review comments on the open pull request
+    #[arg(long, conflicts_with = "staged")]
+    pub post: bool,
+
+    /// Remote the branch is pushed to
+    #[arg(long, default_value = "origin")]
+    pub remote: String,
+
+    /// Exit with status 1 if there is a finding of this severity or higher
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    pub fail_on: Option<Severity>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+impl ReviewArgs {
+    fn base_options(&self) -> BaseOptions<'_> {
+        BaseOptions {
+            base: self.base.as_deref(),
+            base_remote: self.base_remote.as_deref(),
+            remote: &self.remote,
+            fetch: self.fetch,
+            local_base: self.local_base,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase", from = "String")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl From<String> for Severity {
+    /// Models use many names for severities; map them onto the three we report
+    fn from(value: String) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "error" | "critical" | "high" | "major" | "blocker" => Self::Error,
+            "info" | "low" | "minor" | "nit" | "suggestion" | "note" => Self::Info,
+            _ => Self::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single review finding on lines of the new version of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    pub file: String,
+    pub start_line: u32,
+    #[serde(default)]
+    pub end_line: u32,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn lines(&self) -> String {
+        if self.end_line > self.start_line {
+            format!("{}-{}", self.start_line, self.end_line)
+        } else {
+            self.start_line.to_string()
+        }
+    }
+}
+
+/// JSON document printed by `review --output json`
+#[derive(Serialize)]
+struct ReviewOutput<'a> {
+    findings: &'a [Finding],
+    provider: &'a str,
+    model: &'a str,
+    usage: Option<Usage>,
+    duration_ms: u128,
+    pull_request: Option<&'a PullRequest>,
+}
+
+pub async fn run(args: ReviewArgs) -> anyhow::Result<()> {
+    let output = args.output;
+
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    // Get the diff to review
+    let diff = if args.staged {
+        Git::get_staged_diff()
+    } else {
+        let base = args
+            .base
+            .clone()
+            .unwrap_or_else(|| Git::default_branch().unwrap_or_else(|_| "main".to_string()));
+        // The same base as `pr`, so that --post comments on the pull request's diff
+        let (_, base_ref) = resolve_base(&args.base_options(), &base, &|m| output.status(m));
+        output.status(format!(
+            "{} {} → {}",
+            "Reviewing:".dimmed(),
+            base_ref.cyan(),
+            Git::current_branch()?.green()
+        ));
+        Git::get_branch_diff(&base_ref)
+    };
+    let diff = match diff {
+        Ok(d) if !d.trim().is_empty() => d,
+        Ok(_) => {
+            eprintln!("{}", "No changes to review.".yellow());
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+
+    output.status("Reviewing changes...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let started = Instant::now();
+    let completion = client
+        .generate(&build_review_prompt(&diff, &config))
+        .await?;
+    let duration = started.elapsed();
+    let findings = match parse_findings(&completion.text) {
+        Ok(findings) => findings,
+        Err(e) => {
+            eprintln!(
+                "{} Could not parse the review: {}",
+                "Error:".red().bold(),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let posted = if args.post {
+        match post_findings(&args, &config, &diff, &findings, output).await {
+            Ok(pr) => Some(pr),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    if output.is_json() {
+        print_json(&ReviewOutput {
+            findings: &findings,
+            provider: client.provider_name(),
+            model: client.model(),
+            usage: completion.usage,
+            duration_ms: duration.as_millis(),
+            pull_request: posted.as_ref(),
+        })?;
+    } else {
+        print_findings(&findings);
+        if let Some(ref pr) = posted {
+            println!(
+                "\n{} {} {}",
+                "✓".green().bold(),
+                "Posted review:".green(),
+                pr.url
+            );
+        }
+    }
+
+    if let Some(threshold) = args.fail_on {
+        if findings.iter().any(|f| f.severity >= threshold) {
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_findings(findings: &[Finding]) {
+    if findings.is_empty() {
+        println!("\n{}", "✓ No findings.".green());
+        return;
+    }
+
+    let mut by_file: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for finding in findings {
+        by_file.entry(&finding.file).or_default().push(finding);
+    }
+
+    for (file, mut findings) in by_file {
+        findings.sort_by_key(|f| f.start_line);
+        println!("\n{}", file.bold());
+        for finding in findings {
+            let severity = match finding.severity {
+                Severity::Error => "error".red().bold(),
+                Severity::Warning => "warning".yellow().bold(),
+                Severity::Info => "info".blue().bold(),
+            };
+            println!(
+                "  {:>9}  {:<7}  {}",
+                finding.lines().dimmed(),
+                severity,
+                finding.message
+            );
+        }
+    }
+
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    println!(
+        "\n{} finding(s): {} error, {} warning, {} info",
+        findings.len(),
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Info)
+    );
+}
+
+/// Post the findings on the open pull request for the current branch.
+/// Findings outside the diff can't be inline comments and go in the summary.
+async fn post_findings(
+    args: &ReviewArgs,
+    config: &Config,
+    diff: &str,
+    findings: &[Finding],
+    output: OutputFormat,
+) -> anyhow::Result<PullRequest> {
+    let (repo, fork) = forge_repos(&args.base_options())?;
+    let forge = forge::connect(&repo, config)?;
+    let branch = Git::current_branch()?;
+    let Some(pr) = forge.find_open_pull_request(&branch, fork.as_ref()).await? else {
+        anyhow::bail!("No open pull request found for branch '{}'.", branch);
+    };
+
+    let hunks = changed_lines(diff);
+    let (inline, general): (Vec<&Finding>, Vec<&Finding>) = findings.iter().partition(|f| {
+        hunks.get(f.file.as_str()).is_some_and(|ranges| {
+            ranges.iter().any(|(start, end)| {
+                f.start_line.max(1) >= *start && f.end_line.max(f.start_line) <= *end
+            })
+        })
+    });
+
+    let comments: Vec<ReviewComment> = inline
+        .iter()
+        .map(|f| ReviewComment {
+            path: f.file.clone(),
+            start_line: f.start_line,
+            end_line: f.end_line.max(f.start_line),
+            body: format!("**{}**: {}", f.severity, f.message),
+        })
+        .collect();
+
+    let mut summary = format!("git-ai review: {} finding(s).", findings.len());
+    for f in general {
+        summary.push_str(&format!(
+            "\n- **{}** `{}:{}` {}",
+            f.severity,
+            f.file,
+            f.lines(),
+            f.message
+        ));
+    }
+
+    output.status(format!("Posting review on {}...", forge.describe(pr.number)).dimmed());
+    forge.post_review(pr.number, &summary, &comments).await?;
+    Ok(pr)
+}
+
+fn build_review_prompt(diff: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the messages in Korean.",
+        Language::En => "Write the messages in English.",
+    };
+
+    format!(
+        r#"You are an experienced code reviewer. Review the following Git diff.
+
+Instructions:
+- {language_instruction}
+- Report bugs, security problems, missing error handling, performance issues and unclear code.
+- Only comment on added or changed lines. Skip style nitpicks a formatter would fix.
+- Each line of the diff is prefixed with its line number in the new file; use those numbers.
+- Respond with ONLY a JSON array, no other text. Each element has:
+  "file" (path in the new version), "start_line", "end_line",
+  "severity" ("error", "warning" or "info") and "message" (one or two sentences).
+- Respond with [] if there is nothing worth reporting.
+
+Git diff (truncated if too long):
+```
+{diff}
+```"#,
+        language_instruction = language_instruction,
+        diff = annotate_diff(truncate_diff(diff, 12000))
+    )
+}
+
+/// Parse the model's JSON array of findings, tolerating a code fence or
+/// text around it
+fn parse_findings(response: &str) -> Result<Vec<Finding>, serde_json::Error> {
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response.trim(),
+    };
+    let mut findings: Vec<Finding> = serde_json::from_str(json)?;
+    for finding in &mut findings {
+        finding.end_line = finding.end_line.max(finding.start_line);
+    }
+    Ok(findings)
+}
+
+/// Prefix the added and context lines of a diff with their line number in
+/// the new file, so the model can refer to them
+fn annotate_diff(diff: &str) -> String {
+    let mut annotated = String::with_capacity(diff.len() + diff.len() / 4);
+    let mut line_no: Option<u32> = None;
+    for line in diff.lines() {
+        if let Some(start) = hunk_start(line) {
+            line_no = Some(start);
+            annotated.push_str(line);
+        } else if line.starts_with("diff --git") {
+            line_no = None;
+            annotated.push_str(line);
+        } else if let (Some(n), false) = (line_no, line.starts_with('-')) {
+            annotated.push_str(&format!("{:>5} {}", n, line));
+            line_no = Some(n + 1);
+        } else {
+            annotated.push_str(&format!("{:>5} {}", "", line));
+        }
+        annotated.push('\n');
+    }
+    annotated
+}
+
+/// New-file line ranges covered by each file's hunks
+fn changed_lines(diff: &str) -> BTreeMap<&str, Vec<(u32, u32)>> {
+    let mut files: BTreeMap<&str, Vec<(u32, u32)>> = BTreeMap::new();
+    let mut current: Option<&str> = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path.strip_prefix("b/");
+        } else if let (Some(path), Some(range)) = (current, hunk_range(line)) {
+            files.entry(path).or_default().push(range);
+        }
+    }
+    files
+}
+
+/// Start line in the new file of a hunk header like `@@ -1,4 +10,6 @@`
+fn hunk_start(line: &str) -> Option<u32> {
+    hunk_range(line).map(|(start, _)| start)
+}
+
+fn hunk_range(line: &str) -> Option<(u32, u32)> {
+    let rest = line.strip_prefix("@@ ")?;
+    let new = rest.split_whitespace().find(|part| part.starts_with('+'))?;
+    let new = new.trim_start_matches('+');
+    let (start, count) = match new.split_once(',') {
+        Some((start, count)) => (start.parse().ok()?, count.parse().ok()?),
+        None => (new.parse().ok()?, 1),
+    };
+    Some((start, start + count.max(1) - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -10,3 +10,4 @@ fn main() {\n ctx\n-old\n+new\n+more\n ctx\n";
+
+    #[test]
+    fn parse_findings_from_fenced_json() {
+        let response = "Here you go:\n```json\n[{\"file\": \"src/lib.rs\", \"start_line\": 11, \"severity\": \"critical\", \"message\": \"Overflow\"}]\n```";
+        let findings = parse_findings(response).unwrap();
+        assert_eq!(
+            findings,
+            vec![Finding {
+                file: "src/lib.rs".to_string(),
+                start_line: 11,
+                end_line: 11,
+                severity: Severity::Error,
+                message: "Overflow".to_string(),
+            }]
+        );
+        assert!(parse_findings("[]").unwrap().is_empty());
+        assert!(parse_findings("no findings").is_err());
+    }
+
+    #[test]
+    fn annotate_diff_numbers_new_lines() {
+        let annotated = annotate_diff(DIFF);
+        assert!(annotated.contains("   10  ctx\n"));
+        assert!(annotated.contains("      -old\n"));
+        assert!(annotated.contains("   11 +new\n"));
+        assert!(annotated.contains("   13  ctx\n"));
+    }
+
+    #[test]
+    fn changed_lines_from_hunk_headers() {
+        let ranges = changed_lines(DIFF);
+        assert_eq!(ranges.get("src/lib.rs"), Some(&vec![(10, 13)]));
+        assert_eq!(hunk_range("@@ -0,0 +1 @@"), Some((1, 1)));
+    }
+
+    #[test]
+    fn severity_aliases_and_order() {
+        assert_eq!(Severity::from("nit".to_string()), Severity::Info);
+        assert_eq!(Severity::from("HIGH".to_string()), Severity::Error);
+        assert_eq!(Severity::from("medium".to_string()), Severity::Warning);
+        assert!(Severity::Error > Severity::Warning);
+    }
+}
@@ -0,0 +1,575 @@
+use super::commit::{format_instruction, truncate_diff};
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::{Git, GitError};
+use crate::llm::LlmClient;
+use crate::patch::{self, FilePatch};
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Editor, Select};
+use serde::Deserialize;
+use std::io::IsTerminal;
+
+/// Bytes of hunk text shown to the model before hunks are listed by header only
+const PROMPT_BUDGET: usize = 16000;
+
+/// Bytes of a single hunk shown to the model
+const HUNK_BUDGET: usize = 2000;
+
+const PLAN_HELP: &str = "\
+# Each 'commit:' line starts a commit with that subject; the lines below it
+# are the message body. Indented lines assign hunks to the commit by ID.
+# Move hunk lines between commits to regroup them, and edit the messages.
+# Commits without hunks are dropped. Lines starting with '#' are ignored.
+";
+
+#[derive(Args)]
+pub struct SplitArgs {
+    /// Apply the plan without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the plan, don't commit
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+/// A piece of the staged changes that is committed as a whole: one hunk,
+/// or a whole file when its hunks can't be applied separately
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Unit {
+    id: String,
+    file: usize,
+    hunks: Vec<usize>,
+}
+
+/// A commit of the plan, with indices into the units
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Group {
+    message: String,
+    units: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+struct PlanResponse {
+    commits: Vec<PlannedCommit>,
+}
+
+#[derive(Deserialize)]
+struct PlannedCommit {
+    message: String,
+    #[serde(default)]
+    hunks: Vec<String>,
+}
+
+pub async fn run(args: SplitArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    if !Git::has_head() {
+        eprintln!(
+            "{} Nothing to split onto yet; make the initial commit first",
+            "Error:".red().bold()
+        );
+        std::process::exit(1);
+    }
+    let diff = match Git::get_staged_patch() {
+        Ok(diff) => diff,
+        Err(GitError::NoStagedChanges) => {
+            eprintln!("{} No staged changes", "Error:".red().bold());
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    let files = patch::parse(&diff);
+    let units = split_units(&files);
+
+    println!("{}", "Planning commits...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+    let completion = client
+        .generate(&build_split_prompt(&files, &units, &config))
+        .await?;
+    let mut plan = match parse_plan_response(&completion.text) {
+        Ok(planned) => resolve_plan(planned, &units),
+        Err(e) => {
+            eprintln!("{} Failed to parse the plan: {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+
+    loop {
+        print_plan(&plan, &files, &units);
+        if args.dry_run {
+            return Ok(());
+        }
+        if args.yes {
+            break;
+        }
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to apply the plan");
+        }
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Apply this plan?")
+            .items(&["Apply", "Edit the plan", "Cancel"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => break,
+            1 => {
+                let text = render_plan(&plan, &files, &units);
+                let Some(edited) = Editor::new()
+                    .executable(Git::editor()?)
+                    .extension(".txt")
+                    .edit(&text)?
+                else {
+                    continue;
+                };
+                match parse_plan(&edited, &units) {
+                    Ok(edited) if !edited.is_empty() => plan = edited,
+                    Ok(_) => eprintln!("{}", "The edited plan has no commits.".yellow()),
+                    Err(e) => eprintln!("{} {}", "Error:".red().bold(), e),
+                }
+            }
+            _ => {
+                println!("{}", "Aborted.".yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    apply_plan(&plan, &files, &units)?;
+    println!(
+        "\n{} {}",
+        "✓".green().bold(),
+        format!("Created {} commit(s).", plan.len()).green()
+    );
+
+    Ok(())
+}
+
+/// Commit the groups one after another. On failure, HEAD and the index are
+/// restored to how they were before; the working tree is never touched.
+fn apply_plan(plan: &[Group], files: &[FilePatch], units: &[Unit]) -> anyhow::Result<()> {
+    let head = Git::rev_parse("HEAD")?;
+    let tree = Git::write_tree()?;
+
+    let result = (|| -> anyhow::Result<()> {
+        Git::read_tree("HEAD")?;
+        for group in plan {
+            Git::apply_cached(&group_patch(group, files, units))?;
+            Git::commit(&group.message)?;
+            println!(
+                "  {} {}",
+                "✓".green(),
+                group.message.lines().next().unwrap_or_default()
+            );
+        }
+        if Git::write_tree()? != tree {
+            anyhow::bail!("the commits don't add up to the staged changes");
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        eprintln!("{} {}", "Error:".red().bold(), e.to_string().trim());
+        // The staged changes first: they exist nowhere else once the index
+        // moves on, while HEAD can always be reset by hash
+        let restored = Git::read_tree(&tree).and_then(|_| Git::reset_soft(&head));
+        match restored {
+            Ok(()) => eprintln!(
+                "{}",
+                "Rolled back: HEAD and the staged changes are as before.".yellow()
+            ),
+            Err(e) => {
+                eprintln!(
+                    "{} Could not roll back: {}",
+                    "Error:".red().bold(),
+                    e.to_string().trim()
+                );
+                eprintln!("Restore the staged changes and HEAD with:");
+                eprintln!("  git read-tree {}", tree);
+                eprintln!("  git reset --soft {}", head);
+            }
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// One unit per hunk of each splittable file, one unit per other file
+fn split_units(files: &[FilePatch]) -> Vec<Unit> {
+    let mut units = Vec::new();
+    for (file, patch) in files.iter().enumerate() {
+        if patch.is_splittable() {
+            for hunk in 0..patch.hunks.len() {
+                units.push((file, vec![hunk]));
+            }
+        } else {
+            units.push((file, (0..patch.hunks.len()).collect()));
+        }
+    }
+    units
+        .into_iter()
+        .enumerate()
+        .map(|(i, (file, hunks))| Unit {
+            id: format!("H{}", i + 1),
+            file,
+            hunks,
+        })
+        .collect()
+}
+
+/// The patch for a group, with each file's hunks in their original order
+fn group_patch(group: &Group, files: &[FilePatch], units: &[Unit]) -> String {
+    let mut selected: Vec<(usize, Vec<usize>)> = Vec::new();
+    for &u in &group.units {
+        let unit = &units[u];
+        match selected.iter_mut().find(|(file, _)| *file == unit.file) {
+            Some((_, hunks)) => hunks.extend(&unit.hunks),
+            None => selected.push((unit.file, unit.hunks.clone())),
+        }
+    }
+    selected.sort_by_key(|(file, _)| *file);
+
+    selected
+        .into_iter()
+        .map(|(file, mut hunks)| {
+            hunks.sort_unstable();
+            files[file].patch(&hunks)
+        })
+        .collect()
+}
+
+/// A short description of a unit: its file, hunk header and line counts
+fn describe_unit(unit: &Unit, files: &[FilePatch]) -> String {
+    let file = &files[unit.file];
+    let (additions, deletions) = unit.hunks.iter().fold((0, 0), |(a, d), &h| {
+        (a + file.hunks[h].additions, d + file.hunks[h].deletions)
+    });
+    let location = match unit.hunks.as_slice() {
+        [hunk] if file.is_splittable() => {
+            let header = file.hunks[*hunk].header();
+            // Keep "@@ -a,b +c,d @@" without the function context
+            match header[2..].find("@@") {
+                Some(end) => header[..end + 4].to_string(),
+                None => header.to_string(),
+            }
+        }
+        _ => "(whole file)".to_string(),
+    };
+    format!(
+        "{}  {}  (+{} -{})",
+        file.path, location, additions, deletions
+    )
+}
+
+fn print_plan(plan: &[Group], files: &[FilePatch], units: &[Unit]) {
+    println!("\n{}", "Proposed commits:".green().bold());
+    for (i, group) in plan.iter().enumerate() {
+        let mut lines = group.message.lines();
+        println!("\n{}. {}", i + 1, lines.next().unwrap_or_default().bold());
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            println!("   {}", line.dimmed());
+        }
+        for &u in &group.units {
+            println!(
+                "     {} {}",
+                units[u].id.yellow(),
+                describe_unit(&units[u], files)
+            );
+        }
+    }
+    println!();
+}
+
+/// The plan as editable text
+fn render_plan(plan: &[Group], files: &[FilePatch], units: &[Unit]) -> String {
+    let mut text = String::from(PLAN_HELP);
+    for group in plan {
+        let mut lines = group.message.lines();
+        text.push_str(&format!("\ncommit: {}\n", lines.next().unwrap_or_default()));
+        for line in lines {
+            text.push_str(line.trim_start());
+            text.push('\n');
+        }
+        for &u in &group.units {
+            text.push_str(&format!(
+                "  {}  {}\n",
+                units[u].id,
+                describe_unit(&units[u], files)
+            ));
+        }
+    }
+    text
+}
+
+/// Parse an edited plan. Every unit must be assigned to exactly one commit.
+fn parse_plan(text: &str, units: &[Unit]) -> Result<Vec<Group>, String> {
+    let mut plan: Vec<(Vec<String>, Vec<usize>)> = Vec::new();
+    let mut assigned = vec![false; units.len()];
+
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some(subject) = line.strip_prefix("commit:") {
+            plan.push((vec![subject.trim().to_string()], Vec::new()));
+            continue;
+        }
+        let Some((message, group_units)) = plan.last_mut() else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Err(format!("Line before the first 'commit:' line: {}", line));
+        };
+
+        let id = line.split_whitespace().next().unwrap_or_default();
+        let unit = units.iter().position(|u| u.id.eq_ignore_ascii_case(id));
+        match unit {
+            Some(u) if line.starts_with(char::is_whitespace) => {
+                if assigned[u] {
+                    return Err(format!("{} is assigned to more than one commit", id));
+                }
+                assigned[u] = true;
+                group_units.push(u);
+            }
+            _ => message.push(line.trim_end().to_string()),
+        }
+    }
+
+    let missing: Vec<&str> = units
+        .iter()
+        .zip(&assigned)
+        .filter(|(_, assigned)| !**assigned)
+        .map(|(u, _)| u.id.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Not assigned to a commit: {}", missing.join(", ")));
+    }
+
+    Ok(plan
+        .into_iter()
+        .filter(|(_, units)| !units.is_empty())
+        .filter_map(|(lines, units)| {
+            let message = normalize_message(&lines.join("\n"));
+            (!message.is_empty()).then_some(Group { message, units })
+        })
+        .collect())
+}
+
+/// Trim the message and keep a single blank line between subject and body
+fn normalize_message(message: &str) -> String {
+    let message = message.trim();
+    match message.split_once('\n') {
+        Some((subject, body)) if !body.trim().is_empty() => {
+            format!("{}\n\n{}", subject.trim(), body.trim())
+        }
+        _ => message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+fn parse_plan_response(response: &str) -> Result<Vec<PlannedCommit>, serde_json::Error> {
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response.trim(),
+    };
+    let plan: PlanResponse = serde_json::from_str(json)?;
+    Ok(plan.commits)
+}
+
+/// Map the model's plan onto the units. Unknown IDs are ignored, a unit
+/// assigned twice stays in its first commit, and units the model left out
+/// are collected in a last commit.
+fn resolve_plan(planned: Vec<PlannedCommit>, units: &[Unit]) -> Vec<Group> {
+    let mut assigned = vec![false; units.len()];
+    let mut plan = Vec::new();
+    for commit in planned {
+        let mut group = Group {
+            message: normalize_message(&commit.message),
+            units: Vec::new(),
+        };
+        for id in &commit.hunks {
+            let id = id.trim().trim_matches(['[', ']']);
+            if let Some(u) = units.iter().position(|u| u.id.eq_ignore_ascii_case(id)) {
+                if !assigned[u] {
+                    assigned[u] = true;
+                    group.units.push(u);
+                }
+            }
+        }
+        if !group.units.is_empty() && !group.message.is_empty() {
+            plan.push(group);
+        }
+    }
+
+    let rest: Vec<usize> = (0..units.len()).filter(|&u| !assigned[u]).collect();
+    if !rest.is_empty() {
+        plan.push(Group {
+            message: "chore: remaining changes".to_string(),
+            units: rest,
+        });
+    }
+    plan
+}
+
+fn build_split_prompt(files: &[FilePatch], units: &[Unit], config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the commit messages in Korean.",
+        Language::En => "Write the commit messages in English.",
+    };
+    let format_instruction = format_instruction(config.options.format, None);
+
+    let mut hunks = String::new();
+    for unit in units {
+        hunks.push_str(&format!("[{}] {}\n", unit.id, describe_unit(unit, files)));
+        if hunks.len() < PROMPT_BUDGET {
+            let file = &files[unit.file];
+            let text: String = unit
+                .hunks
+                .iter()
+                .map(|&h| file.hunks[h].text.as_str())
+                .collect();
+            hunks.push_str(truncate_diff(&text, HUNK_BUDGET));
+            if !hunks.ends_with('\n') {
+                hunks.push('\n');
+            }
+        }
+        hunks.push('\n');
+    }
+
+    format!(
+        r#"The staged changes below mix several unrelated pieces of work. Split them into a sequence of small, coherent commits.
+
+Each change has an ID in brackets. Changes listed without content were left out for length; group them by file name.
+
+Instructions:
+- {language_instruction}
+- {format_instruction}
+- Group changes that belong together (a feature with its tests, a fix, a refactoring, docs).
+- Assign every ID to exactly one commit. Order the commits so that each one makes sense on its own.
+- Respond with JSON only, in this format:
+{{"commits": [{{"message": "<commit message>", "hunks": ["H1", "H3"]}}]}}
+
+Changes:
+{hunks}"#,
+        language_instruction = language_instruction,
+        format_instruction = format_instruction,
+        hunks = hunks.trim_end()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ a
+-b
++B
+ c
+@@ -20,2 +20,3 @@ fn main() {
+ x
++y
+ z
+diff --git a/docs/new.md b/docs/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/docs/new.md
+@@ -0,0 +1 @@
++hello
+";
+
+    fn units() -> (Vec<FilePatch>, Vec<Unit>) {
+        let files = patch::parse(DIFF);
+        let units = split_units(&files);
+        (files, units)
+    }
+
+    #[test]
+    fn split_units_by_hunk_and_file() {
+        let (_, units) = units();
+        assert_eq!(units.len(), 3);
+        assert_eq!((units[0].file, units[0].hunks.clone()), (0, vec![0]));
+        assert_eq!((units[1].file, units[1].hunks.clone()), (0, vec![1]));
+        assert_eq!((units[2].id.as_str(), units[2].file), ("H3", 1));
+    }
+
+    #[test]
+    fn resolve_plan_collects_unassigned_units() {
+        let (_, units) = units();
+        let planned = parse_plan_response(
+            r#"```json
+{"commits": [
+  {"message": "fix: uppercase b", "hunks": ["H1", "h1", "H9"]},
+  {"message": "docs: add notes", "hunks": ["[H3]"]}
+]}
+```"#,
+        )
+        .unwrap();
+        let plan = resolve_plan(planned, &units);
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].units, vec![0]);
+        assert_eq!(plan[1].units, vec![2]);
+        assert_eq!(plan[2].units, vec![1]);
+    }
+
+    #[test]
+    fn group_patch_orders_hunks_per_file() {
+        let (files, units) = units();
+        let group = Group {
+            message: "x".to_string(),
+            units: vec![2, 1, 0],
+        };
+        let patch = group_patch(&group, &files, &units);
+        assert_eq!(patch, DIFF);
+    }
+
+    #[test]
+    fn render_and_parse_plan_round_trip() {
+        let (files, units) = units();
+        let plan = vec![
+            Group {
+                message: "feat: add y\n\nExplain why.".to_string(),
+                units: vec![1, 2],
+            },
+            Group {
+                message: "fix: uppercase b".to_string(),
+                units: vec![0],
+            },
+        ];
+        let text = render_plan(&plan, &files, &units);
+        assert_eq!(parse_plan(&text, &units).unwrap(), plan);
+
+        let moved = text.replace("  H1 ", "  H9 ");
+        assert!(parse_plan(&moved, &units).unwrap_err().contains("H1"));
+    }
+}
@@ -0,0 +1,419 @@
+use super::commit::{format_instruction, truncate_diff};
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::{Git, GitError};
+use crate::llm::LlmClient;
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Editor, Select};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// First line of the message file git writes when commits are squashed
+const COMBINATION_MARKER: &str = "# This is a combination of";
+
+#[derive(Args)]
+pub struct SquashArgs {
+    /// Branch or commit to squash onto (default: the default branch)
+    #[arg(conflicts_with_all = ["edit_message", "todo"])]
+    pub base: Option<String>,
+
+    /// Commit without asking (or, with --edit-message, without opening the editor)
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the generated message, don't squash
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Squash even if HEAD has already been pushed
+    #[arg(long)]
+    pub force: bool,
+
+    /// Act as GIT_EDITOR: write a generated message when `git rebase -i` squashes commits
+    #[arg(long, value_name = "FILE", conflicts_with = "todo")]
+    pub edit_message: Option<PathBuf>,
+
+    /// Act as GIT_SEQUENCE_EDITOR: squash all commits of the rebase into the first
+    #[arg(long, value_name = "FILE")]
+    pub todo: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+pub async fn run(args: SquashArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    if let Some(ref todo) = args.todo {
+        return edit_todo(todo);
+    }
+    if let Some(ref message_file) = args.edit_message {
+        return edit_message(message_file, &config, args.yes).await;
+    }
+
+    let base = match args.base.clone() {
+        Some(base) => base,
+        None => Git::default_branch()?,
+    };
+    let fork_point = match Git::merge_base(&base, "HEAD") {
+        Ok(hash) => hash,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    let mut commits = Git::get_commit_log(Some(&fork_point), "HEAD")?;
+    if commits.len() < 2 {
+        eprintln!(
+            "{}",
+            format!(
+                "Nothing to squash: {} commit(s) since {}.",
+                commits.len(),
+                base
+            )
+            .yellow()
+        );
+        std::process::exit(1);
+    }
+    commits.reverse();
+
+    match Git::get_staged_diff() {
+        Err(GitError::NoStagedChanges) => {}
+        Ok(_) => {
+            eprintln!(
+                "{} You have staged changes; commit or unstage them first",
+                "Error:".red().bold()
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    }
+    if !args.force && !args.dry_run && Git::is_head_pushed()? {
+        eprintln!(
+            "{} HEAD has already been pushed to a remote branch",
+            "Error:".red().bold()
+        );
+        eprintln!(
+            "{}",
+            "Squashing it rewrites published history. Use --force to squash anyway.".yellow()
+        );
+        std::process::exit(1);
+    }
+
+    let messages: Vec<String> = commits
+        .iter()
+        .map(|c| {
+            if c.body.is_empty() {
+                c.subject.clone()
+            } else {
+                format!("{}\n\n{}", c.subject, c.body)
+            }
+        })
+        .collect();
+    let diff = Git::get_branch_diff(&fork_point)?;
+
+    println!(
+        "{}",
+        format!("Generating a message for {} commits...", commits.len()).dimmed()
+    );
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+    let mut message = generate_message(&client, &messages, &diff, &config).await?;
+
+    println!("\n{}", "Squashed commit message:".green().bold());
+    println!("{}\n", message);
+    if args.dry_run {
+        return Ok(());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to squash");
+        }
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Squash {} commits into one?", commits.len()))
+            .items(&["Squash", "Edit the message", "Cancel"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {}
+            1 => match Editor::new()
+                .executable(Git::editor()?)
+                .extension(".txt")
+                .edit(&message)?
+            {
+                Some(edited) if !strip_comments(&edited).is_empty() => {
+                    message = strip_comments(&edited)
+                }
+                _ => {
+                    println!("{}", "Aborted.".yellow());
+                    return Ok(());
+                }
+            },
+            _ => {
+                println!("{}", "Aborted.".yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    let head = Git::rev_parse("HEAD")?;
+    Git::reset_soft(&fork_point)?;
+    if let Err(e) = Git::commit(&message) {
+        Git::reset_soft(&head)?;
+        eprintln!("{} {}", "Error:".red().bold(), e.to_string().trim());
+        eprintln!("{}", "Rolled back to the original commits.".yellow());
+        std::process::exit(1);
+    }
+
+    println!(
+        "\n{} {}",
+        "✓".green().bold(),
+        format!("Squashed {} commits:", commits.len()).green()
+    );
+    println!("  {}", message.lines().next().unwrap_or_default());
+    println!(
+        "{}",
+        format!(
+            "  Previous HEAD was {}; restore it with 'git reset --soft {}'.",
+            &head[..7],
+            &head[..7]
+        )
+        .dimmed()
+    );
+
+    Ok(())
+}
+
+/// GIT_EDITOR mode. When git asks for the message of squashed commits,
+/// write a generated message above the original ones (commented out), then
+/// let the user edit it. Other messages are passed to the editor unchanged.
+async fn edit_message(path: &Path, config: &Config, yes: bool) -> anyhow::Result<()> {
+    let original = std::fs::read_to_string(path)?;
+
+    let mut content = original.clone();
+    if original.starts_with(COMBINATION_MARKER) {
+        let messages = squashed_messages(&original);
+        let generated = async {
+            let diff = Git::get_amend_diff()?;
+            let client = LlmClient::from_config(config)?;
+            eprintln!(
+                "{}",
+                "git-ai: generating the squashed commit message...".dimmed()
+            );
+            generate_message(&client, &messages, &diff, config).await
+        };
+        match generated.await {
+            Ok(message) => {
+                content = format!("{}\n\n{}", message, comment_out(&original));
+                std::fs::write(path, &content)?;
+            }
+            // Never block the rebase: fall back to git's own message
+            Err(e) => eprintln!("{} {}", "git-ai:".yellow(), e),
+        }
+    }
+
+    if yes || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    // GIT_EDITOR points at us, so open VISUAL or EDITOR instead
+    if let Some(edited) = Editor::new()
+        .require_save(false)
+        .trim_newlines(false)
+        .extension(".txt")
+        .edit(&content)?
+    {
+        std::fs::write(path, edited)?;
+    }
+    Ok(())
+}
+
+/// GIT_SEQUENCE_EDITOR mode: keep the first `pick` and squash the rest into it
+fn edit_todo(path: &Path) -> anyhow::Result<()> {
+    let todo = std::fs::read_to_string(path)?;
+    match squash_todo(&todo) {
+        Some(squashed) => std::fs::write(path, squashed)?,
+        None => eprintln!(
+            "{} The rebase has merges or other commands; leaving the todo list unchanged.",
+            "git-ai:".yellow()
+        ),
+    }
+    Ok(())
+}
+
+/// Turn every `pick` after the first into `squash`. Returns None when the
+/// todo list has commands other than `pick` (e.g. from --rebase-merges).
+fn squash_todo(todo: &str) -> Option<String> {
+    let mut first = true;
+    let mut lines = Vec::new();
+    for line in todo.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            lines.push(line.to_string());
+            continue;
+        }
+        let (command, rest) = trimmed.split_once(' ')?;
+        if !matches!(command, "pick" | "p") {
+            return None;
+        }
+        if first {
+            first = false;
+            lines.push(line.to_string());
+        } else {
+            lines.push(format!("squash {}", rest));
+        }
+    }
+    let mut squashed = lines.join("\n");
+    if todo.ends_with('\n') {
+        squashed.push('\n');
+    }
+    Some(squashed)
+}
+
+/// The messages in git's combined message file, one per squashed commit.
+/// Messages of `fixup` commits are commented out by git and left out.
+fn squashed_messages(content: &str) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        if line.starts_with('#') {
+            if !current.trim().is_empty() {
+                messages.push(current.trim().to_string());
+            }
+            current.clear();
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    if !current.trim().is_empty() {
+        messages.push(current.trim().to_string());
+    }
+    messages
+}
+
+fn comment_out(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                line.to_string()
+            } else if line.is_empty() {
+                "#".to_string()
+            } else {
+                format!("# {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+async fn generate_message(
+    client: &LlmClient,
+    messages: &[String],
+    diff: &str,
+    config: &Config,
+) -> anyhow::Result<String> {
+    let completion = client
+        .generate(&build_squash_prompt(messages, diff, config))
+        .await?;
+    let message = super::pr::strip_code_fence(completion.text.trim()).trim();
+    if message.is_empty() {
+        anyhow::bail!("The model returned an empty message");
+    }
+    Ok(message.to_string())
+}
+
+fn build_squash_prompt(messages: &[String], diff: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the commit message in Korean.",
+        Language::En => "Write the commit message in English.",
+    };
+    let format_instruction = format_instruction(config.options.format, None);
+
+    let messages = messages
+        .iter()
+        .enumerate()
+        .map(|(i, m)| format!("{}. {}", i + 1, m))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"Several commits are being squashed into one. Write a single commit message for the combined change.
+
+The messages of the squashed commits are often work-in-progress notes; use them as hints, but describe the final change shown in the diff.
+
+Instructions:
+- {language_instruction}
+- {format_instruction}
+- Keep the subject line under 72 characters.
+- After a blank line, add a short body with the notable changes as bullet points if the change is not trivial.
+- Output ONLY the commit message, without explanations or markdown code fences.
+
+Squashed commit messages (oldest first):
+{messages}
+
+Combined diff (truncated if too long):
+```
+{diff}
+```"#,
+        language_instruction = language_instruction,
+        format_instruction = format_instruction,
+        messages = messages,
+        diff = truncate_diff(diff, 8000)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squash_todo_keeps_first_pick() {
+        let todo = "pick 1a2b3c4 wip\np 5d6e7f8 more wip\n\n# Rebase 0000000..5d6e7f8\n";
+        assert_eq!(
+            squash_todo(todo).unwrap(),
+            "pick 1a2b3c4 wip\nsquash 5d6e7f8 more wip\n\n# Rebase 0000000..5d6e7f8\n"
+        );
+        assert_eq!(squash_todo("label onto\npick 1a2b3c4 wip\n"), None);
+    }
+
+    #[test]
+    fn squashed_messages_skip_comments() {
+        let content = "# This is a combination of 3 commits.\n# This is the 1st commit message:\n\nwip: login\n\n# This is the commit message #2:\n\nfix tests\n\n# The commit message #3 will be skipped:\n\n# fixup! wip: login\n\n# Please enter the commit message for your changes.\n";
+        assert_eq!(squashed_messages(content), vec!["wip: login", "fix tests"]);
+    }
+
+    #[test]
+    fn comment_out_keeps_existing_comments() {
+        assert_eq!(comment_out("wip\n\n# note"), "# wip\n#\n# note");
+        assert_eq!(
+            strip_comments("feat: x\n# wip\n\nbody\n"),
+            "feat: x\n\nbody"
+        );
+    }
+}
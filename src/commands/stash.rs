@@ -0,0 +1,165 @@
+use super::commit::{format_instruction, parse_suggestions, truncate_diff};
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::Git;
+use crate::llm::LlmClient;
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::io::IsTerminal;
+
+#[derive(Args)]
+pub struct StashArgs {
+    /// Also stash untracked files
+    #[arg(short = 'u', long)]
+    pub include_untracked: bool,
+
+    /// Stash without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the generated message, don't stash
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+pub async fn run(args: StashArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    if !Git::has_head() {
+        eprintln!(
+            "{} There is no commit yet; nothing to stash against",
+            "Error:".red().bold()
+        );
+        std::process::exit(1);
+    }
+    let diff = Git::get_worktree_diff()?;
+    let untracked = if args.include_untracked {
+        Git::untracked_files()?
+    } else {
+        Vec::new()
+    };
+    if diff.trim().is_empty() && untracked.is_empty() {
+        eprintln!("{}", "No local changes to stash.".yellow());
+        std::process::exit(1);
+    }
+
+    println!("{}", "Generating a stash message...".dimmed());
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+    let completion = client
+        .generate(&build_stash_prompt(&diff, &untracked, &config))
+        .await?;
+    let Some(mut message) = stash_message(&completion.text) else {
+        anyhow::bail!("The model returned an empty message");
+    };
+
+    println!("\n{} {}\n", "Stash message:".green().bold(), message);
+    if args.dry_run {
+        return Ok(());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to stash");
+        }
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Stash the changes with this message?")
+            .items(&["Stash", "Edit the message", "Cancel"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {}
+            1 => {
+                message = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Message")
+                    .with_initial_text(&message)
+                    .interact_text()?;
+            }
+            _ => {
+                println!("{}", "Aborted.".yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    Git::stash_push(&message, args.include_untracked)?;
+    println!("{} {} {}", "✓".green().bold(), "Stashed:".green(), message);
+    println!("  {}", "Restore it with: git stash pop".dimmed());
+
+    Ok(())
+}
+
+/// The first message of the response, on a single line as git stash
+/// messages are shown in `git stash list`
+fn stash_message(response: &str) -> Option<String> {
+    let response = super::pr::strip_code_fence(response.trim());
+    let first = parse_suggestions(response).into_iter().next()?;
+    let line = first.lines().next()?.trim().to_string();
+    (!line.is_empty()).then_some(line)
+}
+
+fn build_stash_prompt(diff: &str, untracked: &[String], config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the stash message in Korean.",
+        Language::En => "Write the stash message in English.",
+    };
+    let format_instruction = format_instruction(config.options.format, None);
+
+    let untracked = if untracked.is_empty() {
+        String::new()
+    } else {
+        format!("\n\nNew untracked files:\n{}", untracked.join("\n"))
+    };
+
+    format!(
+        r#"You are writing the message of a git stash, so that the work in progress can be recognized later in `git stash list`.
+
+Instructions:
+- {language_instruction}
+- {format_instruction}
+- Describe what the unfinished work is about, not just which files changed.
+- Write a single line under 72 characters.
+- Output ONLY the message, without explanations or markdown formatting.
+
+Working tree diff (truncated if too long):
+```
+{diff}
+```{untracked}"#,
+        language_instruction = language_instruction,
+        format_instruction = format_instruction,
+        diff = truncate_diff(diff, 4000),
+        untracked = untracked
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stash_message_is_a_single_line() {
+        assert_eq!(
+            stash_message("1. feat: add retry backoff\n2. fix: other").as_deref(),
+            Some("feat: add retry backoff")
+        );
+        assert_eq!(
+            stash_message("```\nwip: parser rewrite\n\nMore details\n```").as_deref(),
+            Some("wip: parser rewrite")
+        );
+        assert_eq!(stash_message("  \n"), None);
+    }
+}
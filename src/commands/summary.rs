@@ -0,0 +1,264 @@
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::{Commit, Git};
+use crate::llm::LlmClient;
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct SummaryArgs {
+    /// Start of the period (any date git understands, e.g. "yesterday", "1 week ago", "2024-05-01")
+    #[arg(long, default_value = "yesterday")]
+    pub since: String,
+
+    /// Only include commits by this author ("me" for your user.email)
+    #[arg(long)]
+    pub author: Option<String>,
+
+    /// Summarize the repositories listed in the config file instead of the current one
+    #[arg(long)]
+    pub repos: bool,
+
+    /// Format of the summary
+    #[arg(long, value_enum, default_value_t = SummaryFormat::Text)]
+    pub format: SummaryFormat,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SummaryFormat {
+    /// Plain text, e.g. for chat messages
+    Text,
+    /// Markdown, e.g. for weekly reports
+    Markdown,
+}
+
+/// Commits of one repository, grouped by the branch they were found on
+struct Activity {
+    name: String,
+    branches: Vec<(String, Vec<Commit>)>,
+}
+
+pub async fn run(args: SummaryArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    let repos = if args.repos || !Git::is_repository() {
+        if config.summary.repos.is_empty() {
+            eprintln!(
+                "{} No repositories configured. Add them with 'git ai config --summary-repo <path>'.",
+                "Error:".red().bold()
+            );
+            std::process::exit(1);
+        }
+        config
+            .summary
+            .repos
+            .iter()
+            .map(|r| expand_home(r))
+            .collect()
+    } else {
+        vec![Git::repo_root()?]
+    };
+
+    let mut activity = Vec::new();
+    for repo in &repos {
+        let author = match args.author.as_deref() {
+            Some("me") => Git::user_email(repo).map(Some),
+            author => Ok(author.map(str::to_string)),
+        };
+        let commits =
+            author.and_then(|author| Git::get_activity(repo, &args.since, author.as_deref()));
+        let commits = match commits {
+            Ok(commits) => commits,
+            Err(e) => {
+                eprintln!(
+                    "{} Skipping {}: {}",
+                    "Warning:".yellow().bold(),
+                    repo.display(),
+                    e.to_string().trim()
+                );
+                continue;
+            }
+        };
+        if !commits.is_empty() {
+            activity.push(Activity {
+                name: repo_name(repo),
+                branches: group_by_branch(commits),
+            });
+        }
+    }
+
+    let total: usize = activity
+        .iter()
+        .flat_map(|a| &a.branches)
+        .map(|(_, commits)| commits.len())
+        .sum();
+    if total == 0 {
+        eprintln!("{}", format!("No commits since {}.", args.since).yellow());
+        std::process::exit(1);
+    }
+
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    eprintln!(
+        "{}",
+        format!(
+            "Summarizing {} commit(s) from {} repositor{}...",
+            total,
+            activity.len(),
+            if activity.len() == 1 { "y" } else { "ies" }
+        )
+        .dimmed()
+    );
+    let completion = client
+        .generate(&build_summary_prompt(&activity, &args.since, &config))
+        .await?;
+    let summary = super::pr::strip_code_fence(completion.text.trim());
+
+    match args.format {
+        SummaryFormat::Markdown => println!("{}", summary.trim()),
+        SummaryFormat::Text => println!("{}", to_plain_text(summary)),
+    }
+
+    Ok(())
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn repo_name(repo: &Path) -> String {
+    repo.canonicalize()
+        .ok()
+        .as_deref()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo.display().to_string())
+}
+
+/// Group commits by branch, keeping the order in which branches first appear
+fn group_by_branch(commits: Vec<(String, Commit)>) -> Vec<(String, Vec<Commit>)> {
+    let mut branches: Vec<(String, Vec<Commit>)> = Vec::new();
+    for (branch, commit) in commits {
+        match branches.iter_mut().find(|(b, _)| *b == branch) {
+            Some((_, commits)) => commits.push(commit),
+            None => branches.push((branch, vec![commit])),
+        }
+    }
+    branches
+}
+
+fn build_summary_prompt(activity: &[Activity], since: &str, config: &Config) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the summary in Korean.",
+        Language::En => "Write the summary in English.",
+    };
+
+    let mut commits = String::new();
+    for repo in activity {
+        commits.push_str(&format!("Repository: {}\n", repo.name));
+        for (branch, branch_commits) in &repo.branches {
+            commits.push_str(&format!("  Branch: {}\n", branch));
+            for c in branch_commits {
+                commits.push_str(&format!(
+                    "    - {} ({}, {})\n",
+                    c.subject,
+                    c.date.get(..10).unwrap_or(&c.date),
+                    c.author
+                ));
+                for line in c.body.lines().filter(|l| !l.trim().is_empty()).take(5) {
+                    commits.push_str(&format!("      {}\n", line.trim()));
+                }
+            }
+        }
+        commits.push('\n');
+    }
+
+    format!(
+        r#"Summarize the following Git activity since {since} for a standup or weekly report.
+
+Instructions:
+- {language_instruction}
+- Group the work by branch or by topic; merge related commits into one bullet.
+- If there are several repositories, start each with a level-2 markdown heading with its name.
+- Start each group with a short bold label (the topic or branch name), followed by bullets.
+- Keep each bullet short and focused on what was achieved, not on individual commits.
+- Mention work in progress on unmerged branches as such.
+- Output markdown only, without any introduction or closing remarks.
+
+Commits:
+{commits}"#,
+        since = since,
+        language_instruction = language_instruction,
+        commits = commits.trim_end()
+    )
+}
+
+/// Turn the markdown summary into plain text for chat messages
+fn to_plain_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    for line in markdown.trim().lines() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let mut text = line.trim_start();
+        if let Some(heading) = text.strip_prefix('#') {
+            text = heading.trim_start_matches('#').trim_start();
+        }
+        let bullet = ["- ", "* ", "+ "].iter().find_map(|b| text.strip_prefix(b));
+        let text = text_without_emphasis(bullet.unwrap_or(text));
+        match bullet {
+            Some(_) => lines.push(format!("{}- {}", indent, text)),
+            None => lines.push(format!("{}{}", indent, text)),
+        }
+    }
+    lines.join("\n")
+}
+
+fn text_without_emphasis(text: &str) -> String {
+    text.replace("**", "").replace("__", "").replace('`', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_by_branch_keeps_first_seen_order() {
+        let groups = group_by_branch(vec![
+            ("feature/login".to_string(), Commit::test("feat: add form")),
+            ("main".to_string(), Commit::test("fix: typo")),
+            ("feature/login".to_string(), Commit::test("feat: validate")),
+        ]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "feature/login");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "main");
+    }
+
+    #[test]
+    fn to_plain_text_strips_markdown() {
+        let markdown =
+            "## api\n\n**Login** (`feature/login`)\n* Added the form\n  - Validated `email`\n";
+        assert_eq!(
+            to_plain_text(markdown),
+            "api\n\nLogin (feature/login)\n- Added the form\n  - Validated email"
+        );
+    }
+}
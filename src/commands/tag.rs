@@ -0,0 +1,248 @@
+use super::commit::{format_instruction, truncate_diff};
+use super::GenerationArgs;
+use crate::config::{Config, Language};
+use crate::git::{Commit, Git};
+use crate::llm::LlmClient;
+use clap::Args;
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Editor, Select};
+use std::io::IsTerminal;
+
+#[derive(Args)]
+pub struct TagArgs {
+    /// Name of the tag to create
+    pub name: String,
+
+    /// Describe the changes since this tag or commit (default: the previous tag)
+    #[arg(long, value_name = "REV")]
+    pub since: Option<String>,
+
+    /// Create the tag without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Only print the generated message, don't create the tag
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub generation: GenerationArgs,
+}
+
+pub async fn run(args: TagArgs) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    args.generation.apply(&mut config.generation);
+
+    if Git::has_tag(&args.name) {
+        eprintln!(
+            "{} Tag '{}' already exists",
+            "Error:".red().bold(),
+            args.name
+        );
+        std::process::exit(1);
+    }
+
+    let previous = args.since.clone().or_else(|| Git::last_tag("HEAD"));
+    let commits = match Git::get_commit_log_without_merges(previous.as_deref(), "HEAD") {
+        Ok(commits) if !commits.is_empty() => commits,
+        Ok(_) => {
+            let since = previous.as_deref().unwrap_or("the start");
+            eprintln!("{}", format!("No commits since {}.", since).yellow());
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
+    // Without a previous tag the commit messages have to do
+    let diff = match previous {
+        Some(ref previous) => Git::get_branch_diff(previous)?,
+        None => String::new(),
+    };
+
+    println!(
+        "{}",
+        format!(
+            "Writing the message for {} ({} commit(s) since {})...",
+            args.name,
+            commits.len(),
+            previous.as_deref().unwrap_or("the start")
+        )
+        .dimmed()
+    );
+    let client = match LlmClient::from_config(&config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{}",
+                "Run 'git ai config' to configure your LLM provider.".yellow()
+            );
+            std::process::exit(1);
+        }
+    };
+    let completion = client
+        .generate(&build_tag_prompt(
+            &args.name,
+            previous.as_deref(),
+            &commits,
+            &diff,
+            &config,
+        ))
+        .await?;
+    let Some(mut message) = tag_message(&completion.text) else {
+        anyhow::bail!("The model returned an empty message");
+    };
+
+    println!("\n{}", "Tag message:".green().bold());
+    println!("{}\n", message);
+    if args.dry_run {
+        return Ok(());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Not a terminal; re-run with --yes to create the tag");
+        }
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Create tag {}?", args.name))
+            .items(&["Create", "Edit the message", "Cancel"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {}
+            1 => match Editor::new()
+                .executable(Git::editor()?)
+                .extension(".txt")
+                .edit(&message)?
+            {
+                Some(edited) if !edited.trim().is_empty() => message = edited.trim().to_string(),
+                _ => {
+                    println!("{}", "Tag not created.".yellow());
+                    return Ok(());
+                }
+            },
+            _ => {
+                println!("{}", "Tag not created.".yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    Git::create_tag(&args.name, &format!("{}\n", message))?;
+    println!(
+        "\n{} {} {}",
+        "✓".green().bold(),
+        "Created tag".green(),
+        args.name.bold()
+    );
+    println!(
+        "  {}",
+        format!("Push it with: git push origin {}", args.name).dimmed()
+    );
+
+    Ok(())
+}
+
+/// The response without surrounding whitespace or a code fence; tag
+/// messages keep all their lines
+fn tag_message(response: &str) -> Option<String> {
+    let message = super::pr::strip_code_fence(response.trim()).trim();
+    (!message.is_empty()).then(|| message.to_string())
+}
+
+fn build_tag_prompt(
+    name: &str,
+    previous: Option<&str>,
+    commits: &[Commit],
+    diff: &str,
+    config: &Config,
+) -> String {
+    let language_instruction = match config.options.language {
+        Language::Ko => "Write the tag message in Korean.",
+        Language::En => "Write the tag message in English.",
+    };
+    let format_instruction = format_instruction(config.options.format, None);
+
+    let since = match previous {
+        Some(previous) => format!("since {}", previous),
+        None => "since the start of the project".to_string(),
+    };
+    let commits = commits
+        .iter()
+        .map(|c| format!("- {}", c.subject))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let diff = if diff.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n\nDiff (truncated if too long):\n```\n{}\n```",
+            truncate_diff(diff, 6000)
+        )
+    };
+
+    format!(
+        r#"Write the message of the annotated git tag {name}, which marks the changes {since}.
+
+Instructions:
+- {language_instruction}
+- The first line summarizes the most important change of the tag in under 72 characters. For this line: {format_instruction}
+- After a blank line, list the notable changes as bullet points, most important first. Merge commits that describe the same change.
+- Output ONLY the tag message, without explanations or markdown code fences.
+
+Commits (newest first):
+{commits}{diff}"#,
+        name = name,
+        since = since,
+        language_instruction = language_instruction,
+        format_instruction = format_instruction,
+        commits = commits,
+        diff = diff
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_message_keeps_all_lines() {
+        assert_eq!(
+            tag_message("```\nAdd code review\n\n- Post findings\n```\n").as_deref(),
+            Some("Add code review\n\n- Post findings")
+        );
+        assert_eq!(tag_message("```\n\n```"), None);
+        assert_eq!(tag_message("  \n"), None);
+    }
+
+    #[test]
+    fn build_tag_prompt_since_previous_tag() {
+        let commits = vec![Commit::test("feat: add review"), Commit::test("fix: typo")];
+        let prompt = build_tag_prompt(
+            "v1.1.0",
+            Some("v1.0.0"),
+            &commits,
+            "diff --git a/src/review.rs b/src/review.rs",
+            &Config::default(),
+        );
+        assert!(prompt.contains("annotated git tag v1.1.0, which marks the changes since v1.0.0."));
+        assert!(prompt.contains("Commits (newest first):\n- feat: add review\n- fix: typo"));
+        assert!(prompt.contains("Diff (truncated if too long):\n```\ndiff --git"));
+    }
+
+    #[test]
+    fn build_tag_prompt_without_previous_tag() {
+        let prompt = build_tag_prompt(
+            "v0.1.0",
+            None,
+            &[Commit::test("feat: initial version")],
+            "",
+            &Config::default(),
+        );
+        assert!(prompt.contains("since the start of the project"));
+        assert!(prompt.ends_with("- feat: initial version"));
+        assert!(!prompt.contains("Diff"));
+    }
+}
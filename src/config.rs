@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum ConfigError {
     #[error("Failed to read config file: {0}")]
     ReadError(#[from] std::io::Error),
@@ -13,19 +14,14 @@ pub enum ConfigError {
     SerializeError(#[from] toml::ser::Error),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
+    #[default]
     En,
     Ko,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Self::En
-    }
-}
-
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,10 +31,11 @@ impl std::fmt::Display for Language {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 pub enum Format {
     /// Conventional Commits without scope: type: description
+    #[default]
     Conventional,
     /// Conventional Commits with scope: type(scope): description
     ConventionalScoped,
@@ -48,12 +45,6 @@ pub enum Format {
     Free,
 }
 
-impl Default for Format {
-    fn default() -> Self {
-        Self::Conventional
-    }
-}
-
 impl std::fmt::Display for Format {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -65,20 +56,15 @@ impl std::fmt::Display for Format {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum AutoStage {
+    #[default]
     Ask,
     Always,
     Never,
 }
 
-impl Default for AutoStage {
-    fn default() -> Self {
-        Self::Ask
-    }
-}
-
 impl std::fmt::Display for AutoStage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -89,12 +75,91 @@ impl std::fmt::Display for AutoStage {
     }
 }
 
+/// Where issue keys are added to generated commit messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueStyle {
+    /// `Refs: PROJ-123` footer
+    Footer,
+    /// `PROJ-123 ` before the subject
+    Prefix,
+    /// Leave commit messages as generated
+    #[default]
+    Off,
+}
+
+impl std::fmt::Display for IssueStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Footer => write!(f, "footer"),
+            Self::Prefix => write!(f, "prefix"),
+            Self::Off => write!(f, "off"),
+        }
+    }
+}
+
+/// Hosting service used to create and update pull requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum ForgeKind {
+    #[serde(rename = "github")]
+    #[value(name = "github")]
+    GitHub,
+    #[serde(rename = "gitlab")]
+    #[value(name = "gitlab")]
+    GitLab,
+    /// Gitea and Forgejo
+    #[serde(rename = "gitea")]
+    #[value(name = "gitea")]
+    Gitea,
+    #[serde(rename = "bitbucket-cloud")]
+    #[value(name = "bitbucket-cloud")]
+    BitbucketCloud,
+    /// Bitbucket Server and Data Center
+    #[serde(rename = "bitbucket-server")]
+    #[value(name = "bitbucket-server")]
+    BitbucketServer,
+}
+
+impl std::fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GitHub => write!(f, "github"),
+            Self::GitLab => write!(f, "gitlab"),
+            Self::Gitea => write!(f, "gitea"),
+            Self::BitbucketCloud => write!(f, "bitbucket-cloud"),
+            Self::BitbucketServer => write!(f, "bitbucket-server"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub provider: ProviderConfig,
     #[serde(default)]
     pub options: OptionsConfig,
+    #[serde(default)]
+    pub generation: GenerationConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    #[serde(default)]
+    pub gitlab: GitLabConfig,
+    #[serde(default)]
+    pub gitea: GiteaConfig,
+    #[serde(default)]
+    pub bitbucket: BitbucketConfig,
+    #[serde(default)]
+    pub forge: ForgeConfig,
+    #[serde(default)]
+    pub issues: IssuesConfig,
+    #[serde(default)]
+    pub risk: RiskConfig,
+    #[serde(default)]
+    pub log: LogConfig,
+    #[serde(default)]
+    pub summary: SummaryConfig,
+    #[serde(default)]
+    pub branch: BranchConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +207,9 @@ pub struct OptionsConfig {
     pub format: Format,
     #[serde(default)]
     pub auto_stage: AutoStage,
+    /// Number of commit message suggestions to generate
+    #[serde(default = "default_suggestions")]
+    pub suggestions: usize,
 }
 
 impl Default for OptionsConfig {
@@ -150,10 +218,192 @@ impl Default for OptionsConfig {
             language: Language::default(),
             format: Format::default(),
             auto_stage: AutoStage::default(),
+            suggestions: default_suggestions(),
         }
     }
 }
 
+fn default_suggestions() -> usize {
+    3
+}
+
+/// Sampling parameters passed to the LLM provider.
+/// Unset values fall back to each provider's defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GenerationConfig {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GitHubConfig {
+    /// REST API base URL, e.g. https://github.example.com/api/v3 for GitHub Enterprise
+    pub api_url: Option<String>,
+    /// API token; GH_TOKEN and GITHUB_TOKEN take precedence
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GitLabConfig {
+    /// Self-hosted GitLab host (e.g. gitlab.example.com) or base URL;
+    /// defaults to the remote's host
+    pub host: Option<String>,
+    /// API token; GITLAB_TOKEN takes precedence
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GiteaConfig {
+    /// Gitea/Forgejo host (e.g. git.example.com) or base URL;
+    /// defaults to the remote's host
+    pub host: Option<String>,
+    /// API token; GITEA_TOKEN takes precedence
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BitbucketConfig {
+    /// Bitbucket Server/Data Center host or base URL; unused for Bitbucket Cloud
+    pub host: Option<String>,
+    /// Access token; BITBUCKET_TOKEN takes precedence
+    pub token: Option<String>,
+    /// Username for Bitbucket Cloud app passwords (sent with the token as basic auth)
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ForgeConfig {
+    /// Forge to use instead of detecting it from the remote URL
+    pub kind: Option<ForgeKind>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuesConfig {
+    /// Regular expressions for issue keys; the first capture group is used if present
+    #[serde(default = "default_issue_patterns")]
+    pub patterns: Vec<String>,
+    /// Jira-style project keys, e.g. "PROJ" to match PROJ-123. Any
+    /// `ABC-123` would also match UTF-8 or SHA-256, so keys are listed.
+    #[serde(default)]
+    pub project_keys: Vec<String>,
+    #[serde(default)]
+    pub style: IssueStyle,
+    /// Add "Closes" and "Refs:" lines to PR descriptions
+    #[serde(default = "default_link_pull_requests")]
+    pub link_pull_requests: bool,
+    /// Keyword for the lines added to PR descriptions, e.g. "Closes #12"
+    #[serde(default = "default_closing_keyword")]
+    pub closing_keyword: String,
+}
+
+impl Default for IssuesConfig {
+    fn default() -> Self {
+        Self {
+            patterns: default_issue_patterns(),
+            project_keys: Vec::new(),
+            style: IssueStyle::default(),
+            link_pull_requests: default_link_pull_requests(),
+            closing_keyword: default_closing_keyword(),
+        }
+    }
+}
+
+fn default_issue_patterns() -> Vec<String> {
+    // GitHub/GitLab issue numbers (#123) and GitLab merge requests (!45)
+    vec![r"#\d+\b".to_string(), r"!\d+\b".to_string()]
+}
+
+fn default_link_pull_requests() -> bool {
+    true
+}
+
+fn default_closing_keyword() -> String {
+    "Closes".to_string()
+}
+
+/// Settings for the risk section and label suggestions of `git ai pr`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskConfig {
+    /// Prefix for the size label (e.g. "size/" suggests "size/M"); empty to disable
+    #[serde(default = "default_size_label_prefix")]
+    pub size_label_prefix: String,
+    /// Labels to suggest when a changed file matches a pattern
+    #[serde(default)]
+    pub labels: Vec<LabelRule>,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            size_label_prefix: default_size_label_prefix(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+fn default_size_label_prefix() -> String {
+    "size/".to_string()
+}
+
+/// Suggest `label` when a changed file matches `pattern` (CODEOWNERS syntax)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelRule {
+    pub pattern: String,
+    pub label: String,
+}
+
+/// Settings for searching history with `git ai log`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LogConfig {
+    /// Rank commits by embedding similarity too (OpenAI and Ollama only)
+    #[serde(default)]
+    pub embeddings: bool,
+    /// Embedding model; defaults to the provider's usual embedding model
+    pub embedding_model: Option<String>,
+}
+
+/// Settings for activity summaries with `git ai summary`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SummaryConfig {
+    /// Repositories summarized with `--repos`; `~/` is expanded to the home directory
+    #[serde(default)]
+    pub repos: Vec<String>,
+}
+
+/// Settings for branch name suggestions with `git ai branch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchConfig {
+    /// Branch name pattern with `{type}`, `{ticket}` and `{slug}` placeholders.
+    /// Without a ticket, `{ticket}` is dropped along with one adjacent separator.
+    #[serde(default = "default_branch_pattern")]
+    pub pattern: String,
+    /// Branch types the model chooses from
+    #[serde(default = "default_branch_types")]
+    pub types: Vec<String>,
+}
+
+impl Default for BranchConfig {
+    fn default() -> Self {
+        Self {
+            pattern: default_branch_pattern(),
+            types: default_branch_types(),
+        }
+    }
+}
+
+fn default_branch_pattern() -> String {
+    "{type}/{ticket}-{slug}".to_string()
+}
+
+fn default_branch_types() -> Vec<String> {
+    ["feature", "fix", "chore", "docs", "refactor"]
+        .iter()
+        .map(|t| t.to_string())
+        .collect()
+}
+
 impl Config {
     pub fn config_dir() -> PathBuf {
         dirs::config_dir()
@@ -0,0 +1,219 @@
+//! Conflict markers left in files by a merge, rebase or cherry-pick
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// One conflicted region of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// Index of the `<<<<<<<` line
+    pub start: usize,
+    /// Index of the line after `>>>>>>>`
+    pub end: usize,
+    pub ours_label: String,
+    pub theirs_label: String,
+    pub ours: String,
+    /// Only present with `merge.conflictStyle = diff3` (or zdiff3)
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Find the conflicts in a file's content. Incomplete conflicts are ignored.
+pub fn parse(content: &str) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let mut current: Option<(Conflict, Section)> = None;
+
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        if let Some(label) = marker(line, OURS_MARKER) {
+            current = Some((
+                Conflict {
+                    start: i,
+                    end: i,
+                    ours_label: label.to_string(),
+                    theirs_label: String::new(),
+                    ours: String::new(),
+                    base: None,
+                    theirs: String::new(),
+                },
+                Section::Ours,
+            ));
+            continue;
+        }
+        let Some((conflict, section)) = current.as_mut() else {
+            continue;
+        };
+
+        // Only the first separator is a marker; later ones, such as a
+        // Markdown underline on their side, are content
+        let is_separator = line.trim_end_matches(['\n', '\r']) == SEPARATOR;
+        if matches!(section, Section::Ours) && marker(line, BASE_MARKER).is_some() {
+            conflict.base = Some(String::new());
+            *section = Section::Base;
+        } else if is_separator && !matches!(section, Section::Theirs) {
+            *section = Section::Theirs;
+        } else if let Some(label) = marker(line, THEIRS_MARKER) {
+            conflict.theirs_label = label.to_string();
+            conflict.end = i + 1;
+            if let Some((conflict, _)) = current.take() {
+                conflicts.push(conflict);
+            }
+        } else {
+            match section {
+                Section::Ours => conflict.ours.push_str(line),
+                Section::Base => conflict.base.get_or_insert_default().push_str(line),
+                Section::Theirs => conflict.theirs.push_str(line),
+            }
+        }
+    }
+    conflicts
+}
+
+/// The label after a conflict marker, if `line` is one
+fn marker<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    let rest = rest.trim_end_matches(['\n', '\r']);
+    if rest.is_empty() {
+        Some(rest)
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+/// Replace the conflicts that have a resolution; the others keep their markers
+pub fn resolve(content: &str, conflicts: &[Conflict], resolutions: &[Option<String>]) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut resolved = String::new();
+    let mut next = 0;
+    for (conflict, resolution) in conflicts.iter().zip(resolutions) {
+        let Some(resolution) = resolution else {
+            continue;
+        };
+        resolved.extend(lines[next..conflict.start].iter().copied());
+        resolved.push_str(resolution);
+        if !resolution.is_empty() && !resolution.ends_with('\n') {
+            resolved.push('\n');
+        }
+        next = conflict.end;
+    }
+    resolved.extend(lines[next..].iter().copied());
+    resolved
+}
+
+/// Find the base version of a conflict in the base file, from the lines
+/// around the conflict. `before` and `after` must be unchanged on all sides.
+pub fn base_hunk(base: &str, before: &[&str], after: &[&str]) -> Option<String> {
+    let lines: Vec<&str> = base.lines().collect();
+    let start = if before.is_empty() {
+        0
+    } else {
+        (before.len()..=lines.len()).find(|&p| lines[p - before.len()..p] == *before)?
+    };
+    let end = if after.is_empty() {
+        lines.len()
+    } else {
+        (start..=lines.len().checked_sub(after.len())?)
+            .find(|&q| lines[q..q + after.len()] == *after)?
+    };
+
+    let mut hunk = lines[start..end].join("\n");
+    if !hunk.is_empty() {
+        hunk.push('\n');
+    }
+    Some(hunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "\
+fn main() {
+<<<<<<< HEAD
+    let retries = 5;
+=======
+    let retries = 3;
+    let delay = 10;
+>>>>>>> feature/retry
+    run(retries);
+<<<<<<< HEAD
+    a();
+||||||| merged common ancestors
+    b();
+=======
+    c();
+>>>>>>> feature/retry
+}
+";
+
+    #[test]
+    fn parse_conflicts_with_and_without_base() {
+        let conflicts = parse(CONTENT);
+        assert_eq!(conflicts.len(), 2);
+
+        let first = &conflicts[0];
+        assert_eq!((first.start, first.end), (1, 7));
+        assert_eq!(first.ours_label, "HEAD");
+        assert_eq!(first.theirs_label, "feature/retry");
+        assert_eq!(first.ours, "    let retries = 5;\n");
+        assert_eq!(first.theirs, "    let retries = 3;\n    let delay = 10;\n");
+        assert_eq!(first.base, None);
+
+        assert_eq!(conflicts[1].base.as_deref(), Some("    b();\n"));
+        assert_eq!(conflicts[1].theirs, "    c();\n");
+    }
+
+    #[test]
+    fn parse_keeps_later_separators_as_content() {
+        let content = "<<<<<<< HEAD\nUsage\n-----\n=======\nUsage\n=======\n>>>>>>> docs\n";
+        let conflicts = parse(content);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours, "Usage\n-----\n");
+        assert_eq!(conflicts[0].theirs, "Usage\n=======\n");
+
+        let resolved = resolve(content, &conflicts, &[Some(conflicts[0].theirs.clone())]);
+        assert_eq!(resolved, "Usage\n=======\n");
+    }
+
+    #[test]
+    fn resolve_replaces_accepted_conflicts_only() {
+        let conflicts = parse(CONTENT);
+        let resolved = resolve(
+            CONTENT,
+            &conflicts,
+            &[
+                Some("    let retries = 5;\n    let delay = 10;".to_string()),
+                None,
+            ],
+        );
+        assert!(resolved.starts_with(
+            "fn main() {\n    let retries = 5;\n    let delay = 10;\n    run(retries);\n<<<<<<< HEAD\n"
+        ));
+        assert_eq!(parse(&resolved).len(), 1);
+
+        let all = resolve(
+            CONTENT,
+            &conflicts,
+            &[Some(String::new()), Some("    a();\n".to_string())],
+        );
+        assert_eq!(all, "fn main() {\n    run(retries);\n    a();\n}\n");
+    }
+
+    #[test]
+    fn base_hunk_between_context_lines() {
+        let base = "fn main() {\n    let retries = 1;\n    run(retries);\n}\n";
+        assert_eq!(
+            base_hunk(base, &["fn main() {"], &["    run(retries);"]).as_deref(),
+            Some("    let retries = 1;\n")
+        );
+        assert_eq!(base_hunk(base, &["missing"], &[]), None);
+        assert_eq!(base_hunk(base, &[], &["fn main() {"]).as_deref(), Some(""));
+    }
+}
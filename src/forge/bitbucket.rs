@@ -0,0 +1,568 @@
+use super::{
+    base_url, check_response, Forge, ForgeError, NewPullRequest, PullRequest, RemoteRepo,
+    ReviewComment,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+pub const DEFAULT_CLOUD_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Client for Bitbucket Cloud (bitbucket.org)
+pub struct BitbucketCloudClient {
+    api_url: String,
+    token: String,
+    username: Option<String>,
+    workspace: String,
+    repo: String,
+    client: reqwest::Client,
+}
+
+impl BitbucketCloudClient {
+    /// With a `username`, the token is sent as an app password over basic auth;
+    /// otherwise it is used as a bearer access token
+    pub fn new(
+        api_url: Option<String>,
+        token: String,
+        username: Option<String>,
+        remote: &RemoteRepo,
+    ) -> Self {
+        Self {
+            api_url: api_url
+                .unwrap_or_else(|| DEFAULT_CLOUD_API_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            token,
+            username,
+            workspace: remote.owner.clone(),
+            repo: remote.name.clone(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn pulls_url(&self, path: &str) -> String {
+        format!(
+            "{}/repositories/{}/{}/pullrequests{}",
+            self.api_url, self.workspace, self.repo, path
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match self.username {
+            Some(ref username) => request.basic_auth(username, Some(&self.token)),
+            None => request.bearer_auth(&self.token),
+        }
+    }
+}
+
+/// Bitbucket Cloud identifies users by `{uuid}` or account ID
+fn cloud_reviewers(reviewers: &[String]) -> Vec<serde_json::Value> {
+    reviewers
+        .iter()
+        .map(|r| {
+            if r.starts_with('{') {
+                json!({ "uuid": r })
+            } else {
+                json!({ "account_id": r })
+            }
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl Forge for BitbucketCloudClient {
+    fn supports_labels(&self) -> bool {
+        false
+    }
+
+    /// Reviewers are account IDs, which usernames can't be resolved to
+    fn supports_owner_reviewers(&self) -> bool {
+        false
+    }
+
+    async fn find_open_pull_request(
+        &self,
+        head: &str,
+        head_repo: Option<&RemoteRepo>,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let mut query = format!("source.branch.name=\"{}\" AND state=\"OPEN\"", head);
+        if let Some(repo) = head_repo {
+            query.push_str(&format!(
+                " AND source.repository.full_name=\"{}\"",
+                repo.path()
+            ));
+        }
+        let response = self
+            .request(reqwest::Method::GET, self.pulls_url(""))
+            .query(&[("q", query)])
+            .send()
+            .await?;
+        let page: CloudPage = check_response(response).await?.json().await?;
+        Ok(page.values.into_iter().next().map(Into::into))
+    }
+
+    async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest, ForgeError> {
+        let mut request = json!({
+            "title": pr.title,
+            "description": pr.body,
+            "source": { "branch": { "name": pr.head } },
+            "destination": { "branch": { "name": pr.base } },
+            "draft": pr.draft,
+        });
+        if let Some(ref fork) = pr.head_repo {
+            request["source"]["repository"] = json!({ "full_name": fork.path() });
+        }
+        if !pr.reviewers.is_empty() {
+            request["reviewers"] = json!(cloud_reviewers(&pr.reviewers));
+        }
+        let response = self
+            .request(reqwest::Method::POST, self.pulls_url(""))
+            .json(&request)
+            .send()
+            .await?;
+        let created: CloudPullRequest = check_response(response).await?.json().await?;
+        Ok(created.into())
+    }
+
+    async fn update_pull_request(
+        &self,
+        number: u64,
+        pr: &NewPullRequest,
+    ) -> Result<PullRequest, ForgeError> {
+        let mut request = json!({
+            "title": pr.title,
+            "description": pr.body,
+            "destination": { "branch": { "name": pr.base } },
+        });
+        if !pr.reviewers.is_empty() {
+            request["reviewers"] = json!(cloud_reviewers(&pr.reviewers));
+        }
+        let response = self
+            .request(
+                reqwest::Method::PUT,
+                self.pulls_url(&format!("/{}", number)),
+            )
+            .json(&request)
+            .send()
+            .await?;
+        let updated: CloudPullRequest = check_response(response).await?.json().await?;
+        Ok(updated.into())
+    }
+
+    async fn post_review(
+        &self,
+        number: u64,
+        summary: &str,
+        comments: &[ReviewComment],
+    ) -> Result<(), ForgeError> {
+        let url = self.pulls_url(&format!("/{}/comments", number));
+        let mut requests: Vec<serde_json::Value> = comments
+            .iter()
+            .map(|comment| {
+                json!({
+                    "content": { "raw": comment.body },
+                    "inline": { "path": comment.path, "to": comment.end_line },
+                })
+            })
+            .collect();
+        requests.push(json!({ "content": { "raw": summary } }));
+
+        for request in requests {
+            let response = self
+                .request(reqwest::Method::POST, url.clone())
+                .json(&request)
+                .send()
+                .await?;
+            check_response(response).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct CloudPage {
+    values: Vec<CloudPullRequest>,
+}
+
+#[derive(Deserialize)]
+struct CloudPullRequest {
+    id: u64,
+    links: CloudLinks,
+    #[serde(default)]
+    title: String,
+    description: Option<String>,
+    destination: Option<CloudEndpoint>,
+}
+
+#[derive(Deserialize)]
+struct CloudEndpoint {
+    branch: CloudBranch,
+}
+
+#[derive(Deserialize)]
+struct CloudBranch {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct CloudLinks {
+    html: Link,
+}
+
+#[derive(Deserialize)]
+struct Link {
+    href: String,
+}
+
+impl From<CloudPullRequest> for PullRequest {
+    fn from(pr: CloudPullRequest) -> Self {
+        Self {
+            number: pr.id,
+            url: pr.links.html.href,
+            title: pr.title,
+            body: pr.description.unwrap_or_default(),
+            base: pr.destination.map(|d| d.branch.name).unwrap_or_default(),
+        }
+    }
+}
+
+/// Client for Bitbucket Server and Data Center
+pub struct BitbucketServerClient {
+    api_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl BitbucketServerClient {
+    /// `host` is a hostname or a full base URL (including any context path)
+    pub fn new(host: &str, token: String, remote: &RemoteRepo) -> Self {
+        let project = project_key(remote);
+        Self {
+            api_url: format!(
+                "{}/rest/api/1.0/projects/{}/repos/{}",
+                base_url(host),
+                project,
+                remote.name
+            ),
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn pulls_url(&self, path: &str) -> String {
+        format!("{}/pull-requests{}", self.api_url, path)
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        self.client.request(method, url).bearer_auth(&self.token)
+    }
+}
+
+/// HTTP clone URLs look like https://host/scm/PROJ/repo.git
+fn project_key(remote: &RemoteRepo) -> &str {
+    remote.owner.strip_prefix("scm/").unwrap_or(&remote.owner)
+}
+
+/// A ref of a pull request, in another repository when the branch is in a fork
+fn server_ref(branch: &str, repo: Option<&RemoteRepo>) -> serde_json::Value {
+    let mut value = json!({ "id": format!("refs/heads/{}", branch) });
+    if let Some(repo) = repo {
+        value["repository"] = json!({
+            "slug": repo.name,
+            "project": { "key": project_key(repo) },
+        });
+    }
+    value
+}
+
+fn server_reviewers(reviewers: &[String]) -> Vec<serde_json::Value> {
+    reviewers
+        .iter()
+        .map(|name| json!({ "user": { "name": name.trim_start_matches('@') } }))
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl Forge for BitbucketServerClient {
+    fn supports_labels(&self) -> bool {
+        false
+    }
+
+    async fn find_open_pull_request(
+        &self,
+        head: &str,
+        head_repo: Option<&RemoteRepo>,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let at = format!("refs/heads/{}", head);
+        let Some(fork) = head_repo else {
+            let response = self
+                .request(reqwest::Method::GET, self.pulls_url(""))
+                .query(&[
+                    ("at", at.as_str()),
+                    ("direction", "OUTGOING"),
+                    ("state", "OPEN"),
+                ])
+                .send()
+                .await?;
+            let page: ServerPage = check_response(response).await?.json().await?;
+            return Ok(page.values.into_iter().next().map(Into::into));
+        };
+
+        // Pull requests from a fork are incoming to this repository
+        let mut start = 0;
+        loop {
+            let response = self
+                .request(reqwest::Method::GET, self.pulls_url(""))
+                .query(&[
+                    ("direction", "INCOMING"),
+                    ("state", "OPEN"),
+                    ("limit", "100"),
+                ])
+                .query(&[("start", start)])
+                .send()
+                .await?;
+            let page: ServerPage = check_response(response).await?.json().await?;
+            let matching = page.values.into_iter().find(|pr| {
+                pr.from_ref.as_ref().is_some_and(|from| {
+                    from.id == at
+                        && from.repository.as_ref().is_some_and(|repo| {
+                            repo.slug.eq_ignore_ascii_case(&fork.name)
+                                && repo.project.key.eq_ignore_ascii_case(project_key(fork))
+                        })
+                })
+            });
+            if let Some(pr) = matching {
+                return Ok(Some(pr.into()));
+            }
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next,
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest, ForgeError> {
+        let request = json!({
+            "title": pr.title,
+            "description": pr.body,
+            "fromRef": server_ref(&pr.head, pr.head_repo.as_ref()),
+            "toRef": { "id": format!("refs/heads/{}", pr.base) },
+            "draft": pr.draft,
+            "reviewers": server_reviewers(&pr.reviewers),
+        });
+        let response = self
+            .request(reqwest::Method::POST, self.pulls_url(""))
+            .json(&request)
+            .send()
+            .await?;
+        let created: ServerPullRequest = check_response(response).await?.json().await?;
+        Ok(created.into())
+    }
+
+    async fn update_pull_request(
+        &self,
+        number: u64,
+        pr: &NewPullRequest,
+    ) -> Result<PullRequest, ForgeError> {
+        // Updates must carry the current version for optimistic locking
+        let url = self.pulls_url(&format!("/{}", number));
+        let response = self
+            .request(reqwest::Method::GET, url.clone())
+            .send()
+            .await?;
+        let current: ServerPullRequest = check_response(response).await?.json().await?;
+
+        let mut request = json!({
+            "version": current.version,
+            "title": pr.title,
+            "description": pr.body,
+            "toRef": { "id": format!("refs/heads/{}", pr.base) },
+        });
+        if !pr.reviewers.is_empty() {
+            request["reviewers"] = json!(server_reviewers(&pr.reviewers));
+        }
+        let response = self
+            .request(reqwest::Method::PUT, url)
+            .json(&request)
+            .send()
+            .await?;
+        let updated: ServerPullRequest = check_response(response).await?.json().await?;
+        Ok(updated.into())
+    }
+}
+
+#[derive(Deserialize)]
+struct ServerPage {
+    values: Vec<ServerPullRequest>,
+    #[serde(default, rename = "isLastPage")]
+    is_last_page: bool,
+    #[serde(rename = "nextPageStart")]
+    next_page_start: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ServerPullRequest {
+    id: u64,
+    #[serde(default)]
+    version: u64,
+    links: ServerLinks,
+    #[serde(default)]
+    title: String,
+    description: Option<String>,
+    #[serde(rename = "fromRef")]
+    from_ref: Option<ServerRef>,
+    #[serde(rename = "toRef")]
+    to_ref: Option<ServerRef>,
+}
+
+#[derive(Deserialize)]
+struct ServerRef {
+    #[serde(default)]
+    id: String,
+    #[serde(rename = "displayId")]
+    display_id: String,
+    repository: Option<ServerRepository>,
+}
+
+#[derive(Deserialize)]
+struct ServerRepository {
+    slug: String,
+    project: ServerProject,
+}
+
+#[derive(Deserialize)]
+struct ServerProject {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct ServerLinks {
+    #[serde(rename = "self")]
+    self_links: Vec<Link>,
+}
+
+impl From<ServerPullRequest> for PullRequest {
+    fn from(pr: ServerPullRequest) -> Self {
+        Self {
+            number: pr.id,
+            url: pr
+                .links
+                .self_links
+                .into_iter()
+                .next()
+                .map(|link| link.href)
+                .unwrap_or_default(),
+            title: pr.title,
+            body: pr.description.unwrap_or_default(),
+            base: pr.to_ref.map(|r| r.display_id).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::tests::new_pr;
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn cloud_create_pull_request() {
+        let server = MockServer::start(vec![(
+            201,
+            r#"{"id": 8, "links": {"html": {"href": "https://bitbucket.org/ws/app/pull-requests/8"}}}"#,
+        )]);
+        let remote = RemoteRepo::parse("git@bitbucket.org:ws/app.git").unwrap();
+        let client = BitbucketCloudClient::new(
+            Some(server.url.clone()),
+            "secret".to_string(),
+            Some("me".to_string()),
+            &remote,
+        );
+
+        let pr = client
+            .create_pull_request(&new_pr(&[], &["{a1b2}"]))
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 8);
+        assert_eq!(pr.url, "https://bitbucket.org/ws/app/pull-requests/8");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/repositories/ws/app/pullrequests");
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Basic bWU6c2VjcmV0")
+        );
+        let body = requests[0].json();
+        assert_eq!(body["source"]["branch"]["name"], "feature/login");
+        assert_eq!(body["destination"]["branch"]["name"], "main");
+        assert_eq!(body["reviewers"][0]["uuid"], "{a1b2}");
+    }
+
+    #[tokio::test]
+    async fn server_update_sends_current_version() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"{"id": 3, "version": 5, "links": {"self": [{"href": "https://bb/pr/3"}]}}"#,
+            ),
+            (
+                200,
+                r#"{"id": 3, "version": 6, "links": {"self": [{"href": "https://bb/pr/3"}]}}"#,
+            ),
+        ]);
+        let remote = RemoteRepo::parse("https://bitbucket.example.com/scm/PROJ/app.git").unwrap();
+        let client = BitbucketServerClient::new(&server.url, "tok".to_string(), &remote);
+
+        let pr = new_pr(&[], &[]);
+        let updated = client.update_pull_request(3, &pr).await.unwrap();
+        assert_eq!(updated.url, "https://bb/pr/3");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(
+            requests[0].path,
+            "/rest/api/1.0/projects/PROJ/repos/app/pull-requests/3"
+        );
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(requests[1].json()["version"], 5);
+        assert!(requests[1].json().get("reviewers").is_none());
+    }
+
+    #[tokio::test]
+    async fn server_find_pull_request_from_fork() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"{"values": [
+                    {"id": 1, "links": {"self": [{"href": "u1"}]},
+                     "fromRef": {"id": "refs/heads/feature/login", "displayId": "feature/login",
+                                 "repository": {"slug": "app", "project": {"key": "PROJ"}}}}
+                ], "isLastPage": false, "nextPageStart": 100}"#,
+            ),
+            (
+                200,
+                r#"{"values": [
+                    {"id": 2, "links": {"self": [{"href": "u2"}]},
+                     "fromRef": {"id": "refs/heads/feature/login", "displayId": "feature/login",
+                                 "repository": {"slug": "app", "project": {"key": "~ALICE"}}}}
+                ], "isLastPage": true}"#,
+            ),
+        ]);
+        let remote = RemoteRepo::parse("https://bitbucket.example.com/scm/PROJ/app.git").unwrap();
+        let fork = RemoteRepo::parse("https://bitbucket.example.com/scm/~alice/app.git").unwrap();
+        let client = BitbucketServerClient::new(&server.url, "tok".to_string(), &remote);
+
+        let found = client
+            .find_open_pull_request("feature/login", Some(&fork))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.number, 2);
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/rest/api/1.0/projects/PROJ/repos/app/pull-requests?direction=INCOMING&state=OPEN&limit=100&start=0"
+        );
+        assert!(requests[1].path.ends_with("&start=100"));
+    }
+}
@@ -0,0 +1,407 @@
+use super::{
+    base_url, check_response, qualified_head, Forge, ForgeError, NewPullRequest, PullRequest,
+    RemoteRepo, ReviewComment,
+};
+use serde::{Deserialize, Serialize};
+
+/// Client for Gitea and Forgejo, which share the same API
+pub struct GiteaClient {
+    api_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+    client: reqwest::Client,
+}
+
+impl GiteaClient {
+    /// `host` is a hostname (`git.example.com`) or a full base URL;
+    /// the API lives under `/api/v1`
+    pub fn new(host: &str, token: String, remote: &RemoteRepo) -> Self {
+        Self {
+            api_url: format!("{}/api/v1", base_url(host)),
+            token,
+            owner: remote.owner.clone(),
+            repo: remote.name.clone(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn repo_url(&self, path: &str) -> String {
+        format!(
+            "{}/repos/{}/{}{}",
+            self.api_url, self.owner, self.repo, path
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("Authorization", format!("token {}", self.token))
+    }
+
+    /// Resolve label names to IDs, which the pull request API expects
+    async fn label_ids(&self, names: &[String]) -> Result<Option<Vec<u64>>, ForgeError> {
+        if names.is_empty() {
+            return Ok(None);
+        }
+
+        let response = self
+            .request(reqwest::Method::GET, self.repo_url("/labels"))
+            .query(&[("limit", "100")])
+            .send()
+            .await?;
+        let labels: Vec<LabelResponse> = check_response(response).await?.json().await?;
+
+        names
+            .iter()
+            .map(|name| {
+                labels
+                    .iter()
+                    .find(|label| label.name.eq_ignore_ascii_case(name))
+                    .map(|label| label.id)
+                    .ok_or_else(|| ForgeError::ApiError {
+                        status: 404,
+                        message: format!("Label not found: {}", name),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    async fn request_reviewers(&self, number: u64, reviewers: &[String]) -> Result<(), ForgeError> {
+        if reviewers.is_empty() {
+            return Ok(());
+        }
+
+        let (teams, users): (Vec<&String>, Vec<&String>) =
+            reviewers.iter().partition(|r| r.contains('/'));
+        let team_names: Vec<&str> = teams.iter().filter_map(|t| t.rsplit('/').next()).collect();
+
+        let url = self.repo_url(&format!("/pulls/{}/requested_reviewers", number));
+        let response = self
+            .request(reqwest::Method::POST, url)
+            .json(&serde_json::json!({
+                "reviewers": users,
+                "team_reviewers": team_names,
+            }))
+            .send()
+            .await?;
+        check_response(response).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GiteaClient {
+    fn supports_team_reviewers(&self) -> bool {
+        true
+    }
+
+    async fn current_user(&self) -> Result<Option<String>, ForgeError> {
+        let url = format!("{}/user", self.api_url);
+        let response = self.request(reqwest::Method::GET, url).send().await?;
+        let user: UserResponse = check_response(response).await?.json().await?;
+        Ok(Some(user.login))
+    }
+
+    async fn find_open_pull_request(
+        &self,
+        head: &str,
+        head_repo: Option<&RemoteRepo>,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let repo =
+            head_repo.map_or_else(|| format!("{}/{}", self.owner, self.repo), RemoteRepo::path);
+        // Pages until an empty one: the server may cap the limit below ours
+        for page in 1.. {
+            let response = self
+                .request(reqwest::Method::GET, self.repo_url("/pulls"))
+                .query(&[("state", "open"), ("limit", "50")])
+                .query(&[("page", page)])
+                .send()
+                .await?;
+            let found: Vec<PullResponse> = check_response(response).await?.json().await?;
+            if found.is_empty() {
+                break;
+            }
+            let matching = found.into_iter().find(|pr| {
+                pr.head.as_ref().is_some_and(|h| {
+                    h.branch == head
+                        && h.repo
+                            .iter()
+                            .all(|r| r.full_name.eq_ignore_ascii_case(&repo))
+                })
+            });
+            if let Some(pr) = matching {
+                return Ok(Some(pr.into()));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest, ForgeError> {
+        let title = draft_title(&pr.title, pr.draft);
+        let head = qualified_head(&pr.head, pr.head_repo.as_ref());
+        let request = PullRequestBody {
+            head: Some(&head),
+            base: &pr.base,
+            title: &title,
+            body: &pr.body,
+            labels: self.label_ids(&pr.labels).await?,
+        };
+        let response = self
+            .request(reqwest::Method::POST, self.repo_url("/pulls"))
+            .json(&request)
+            .send()
+            .await?;
+        let created: PullResponse = check_response(response).await?.json().await?;
+        self.request_reviewers(created.number, &pr.reviewers)
+            .await?;
+        Ok(created.into())
+    }
+
+    async fn update_pull_request(
+        &self,
+        number: u64,
+        pr: &NewPullRequest,
+    ) -> Result<PullRequest, ForgeError> {
+        let title = draft_title(&pr.title, pr.draft);
+        let request = PullRequestBody {
+            head: None,
+            base: &pr.base,
+            title: &title,
+            body: &pr.body,
+            labels: self.label_ids(&pr.labels).await?,
+        };
+        let url = self.repo_url(&format!("/pulls/{}", number));
+        let response = self
+            .request(reqwest::Method::PATCH, url)
+            .json(&request)
+            .send()
+            .await?;
+        let updated: PullResponse = check_response(response).await?.json().await?;
+        self.request_reviewers(number, &pr.reviewers).await?;
+        Ok(updated.into())
+    }
+
+    async fn post_review(
+        &self,
+        number: u64,
+        summary: &str,
+        comments: &[ReviewComment],
+    ) -> Result<(), ForgeError> {
+        let comments: Vec<serde_json::Value> = comments
+            .iter()
+            .map(|comment| {
+                serde_json::json!({
+                    "path": comment.path,
+                    "body": comment.body,
+                    "new_position": comment.end_line,
+                })
+            })
+            .collect();
+        let url = self.repo_url(&format!("/pulls/{}/reviews", number));
+        let response = self
+            .request(reqwest::Method::POST, url)
+            .json(&serde_json::json!({
+                "event": "COMMENT",
+                "body": summary,
+                "comments": comments,
+            }))
+            .send()
+            .await?;
+        check_response(response).await?;
+        Ok(())
+    }
+}
+
+/// Gitea marks drafts with a work-in-progress title prefix
+fn draft_title(title: &str, draft: bool) -> String {
+    if draft && !title.starts_with("WIP:") {
+        format!("WIP: {}", title)
+    } else {
+        title.to_string()
+    }
+}
+
+#[derive(Serialize)]
+struct PullRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    head: Option<&'a str>,
+    base: &'a str,
+    title: &'a str,
+    body: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<Vec<u64>>,
+}
+
+#[derive(Deserialize)]
+struct PullResponse {
+    number: u64,
+    html_url: String,
+    #[serde(default)]
+    title: String,
+    body: Option<String>,
+    head: Option<BranchRef>,
+    base: Option<BranchRef>,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    #[serde(rename = "ref")]
+    branch: String,
+    repo: Option<RepoRef>,
+}
+
+#[derive(Deserialize)]
+struct RepoRef {
+    full_name: String,
+}
+
+impl From<PullResponse> for PullRequest {
+    fn from(pr: PullResponse) -> Self {
+        Self {
+            number: pr.number,
+            url: pr.html_url,
+            title: pr.title,
+            body: pr.body.unwrap_or_default(),
+            base: pr.base.map(|b| b.branch).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct LabelResponse {
+    id: u64,
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::tests::{new_pr, repo};
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn create_pull_request_resolves_labels() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"[{"id": 1, "name": "bug"}, {"id": 4, "name": "feature"}]"#,
+            ),
+            (
+                201,
+                r#"{"number": 12, "html_url": "https://codeberg.org/team/app/pulls/12"}"#,
+            ),
+            (201, "{}"),
+        ]);
+        let client = GiteaClient::new(
+            &server.url,
+            "tok".to_string(),
+            &repo("codeberg.org", "team", "app"),
+        );
+
+        let pr = client
+            .create_pull_request(&new_pr(&["Feature"], &["alice"]))
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 12);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/api/v1/repos/team/app/labels?limit=100");
+        assert_eq!(requests[1].path, "/api/v1/repos/team/app/pulls");
+        assert_eq!(requests[1].header("authorization"), Some("token tok"));
+        let body = requests[1].json();
+        assert_eq!(body["title"], "WIP: Add login");
+        assert_eq!(body["head"], "feature/login");
+        assert_eq!(body["labels"][0], 4);
+        assert_eq!(
+            requests[2].path,
+            "/api/v1/repos/team/app/pulls/12/requested_reviewers"
+        );
+    }
+
+    #[tokio::test]
+    async fn find_open_pull_request_matches_head_branch() {
+        let server = MockServer::start(vec![(
+            200,
+            r#"[
+                {"number": 1, "html_url": "u1", "head": {"ref": "other"}},
+                {"number": 2, "html_url": "u2", "head": {"ref": "feature/login"}}
+            ]"#,
+        )]);
+        let client = GiteaClient::new(
+            &server.url,
+            "tok".to_string(),
+            &repo("codeberg.org", "team", "app"),
+        );
+
+        let found = client
+            .find_open_pull_request("feature/login", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.number, 2);
+        assert_eq!(found.url, "u2");
+    }
+
+    #[tokio::test]
+    async fn find_open_pull_request_from_fork_matches_head_repo() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"[{"number": 1, "html_url": "u1", "head": {"ref": "feature/login", "repo": {"full_name": "team/app"}}}]"#,
+            ),
+            (
+                200,
+                r#"[{"number": 2, "html_url": "u2", "head": {"ref": "feature/login", "repo": {"full_name": "alice/app"}}}]"#,
+            ),
+        ]);
+        let client = GiteaClient::new(
+            &server.url,
+            "tok".to_string(),
+            &repo("codeberg.org", "team", "app"),
+        );
+        let fork = RemoteRepo::parse("https://codeberg.org/alice/app.git").unwrap();
+
+        let found = client
+            .find_open_pull_request("feature/login", Some(&fork))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.number, 2);
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/api/v1/repos/team/app/pulls?state=open&limit=50&page=1"
+        );
+        assert!(requests[1].path.ends_with("&page=2"));
+    }
+
+    #[tokio::test]
+    async fn find_open_pull_request_stops_at_empty_page() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"[{"number": 1, "html_url": "u1", "head": {"ref": "other"}}]"#,
+            ),
+            (200, "[]"),
+        ]);
+        let client = GiteaClient::new(
+            &server.url,
+            "tok".to_string(),
+            &repo("codeberg.org", "team", "app"),
+        );
+
+        let found = client
+            .find_open_pull_request("feature/login", None)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+        assert_eq!(server.requests().len(), 2);
+    }
+}
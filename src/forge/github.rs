@@ -0,0 +1,427 @@
+use super::{
+    check_response, qualified_head, Forge, ForgeError, NewPullRequest, PullRequest, RemoteRepo,
+    ReviewComment,
+};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+pub struct GitHubClient {
+    api_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+    client: reqwest::Client,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: Option<String>, token: String, remote: &RemoteRepo) -> Self {
+        Self {
+            api_url: api_url
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            token,
+            owner: remote.owner.clone(),
+            repo: remote.name.clone(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn repo_url(&self, path: &str) -> String {
+        format!(
+            "{}/repos/{}/{}{}",
+            self.api_url, self.owner, self.repo, path
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "git-ai")
+    }
+
+    fn post<T: Serialize + ?Sized>(&self, url: String, body: &T) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::POST, url).json(body)
+    }
+
+    /// Apply labels and request reviewers after creating or updating
+    async fn decorate(&self, number: u64, pr: &NewPullRequest) -> Result<(), ForgeError> {
+        if !pr.labels.is_empty() {
+            let url = self.repo_url(&format!("/issues/{}/labels", number));
+            let response = self
+                .post(url, &serde_json::json!({ "labels": pr.labels }))
+                .send()
+                .await?;
+            check_response(response).await?;
+        }
+
+        if !pr.reviewers.is_empty() {
+            self.request_reviewers(number, &pr.reviewers).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Request reviews from users or teams (`org/team`)
+    pub async fn request_reviewers(
+        &self,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<(), ForgeError> {
+        let (teams, users): (Vec<&String>, Vec<&String>) =
+            reviewers.iter().partition(|r| r.contains('/'));
+        let team_slugs: Vec<&str> = teams.iter().filter_map(|t| t.rsplit('/').next()).collect();
+
+        let url = self.repo_url(&format!("/pulls/{}/requested_reviewers", number));
+        let body = serde_json::json!({
+            "reviewers": users,
+            "team_reviewers": team_slugs,
+        });
+        let response = self.post(url, &body).send().await?;
+        check_response(response).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GitHubClient {
+    fn supports_team_reviewers(&self) -> bool {
+        true
+    }
+
+    async fn current_user(&self) -> Result<Option<String>, ForgeError> {
+        let url = format!("{}/user", self.api_url);
+        let response = self.request(reqwest::Method::GET, url).send().await?;
+        let user: UserResponse = check_response(response).await?.json().await?;
+        Ok(Some(user.login))
+    }
+
+    async fn find_open_pull_request(
+        &self,
+        head: &str,
+        head_repo: Option<&RemoteRepo>,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let owner = head_repo.map_or(&self.owner, |repo| &repo.owner);
+        let head = format!("{}:{}", owner, head);
+        let response = self
+            .request(reqwest::Method::GET, self.repo_url("/pulls"))
+            .query(&[("state", "open"), ("head", head.as_str())])
+            .send()
+            .await?;
+        let found: Vec<PullResponse> = check_response(response).await?.json().await?;
+        Ok(found.into_iter().next().map(Into::into))
+    }
+
+    /// Open a pull request, then apply labels and request reviewers
+    async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest, ForgeError> {
+        let head = qualified_head(&pr.head, pr.head_repo.as_ref());
+        let request = CreatePullRequest {
+            title: &pr.title,
+            body: &pr.body,
+            head: &head,
+            base: &pr.base,
+            draft: pr.draft,
+        };
+        let response = self.post(self.repo_url("/pulls"), &request).send().await?;
+        let created: PullResponse = check_response(response).await?.json().await?;
+        self.decorate(created.number, pr).await?;
+        Ok(created.into())
+    }
+
+    async fn update_pull_request(
+        &self,
+        number: u64,
+        pr: &NewPullRequest,
+    ) -> Result<PullRequest, ForgeError> {
+        let request = serde_json::json!({
+            "title": pr.title,
+            "body": pr.body,
+            "base": pr.base,
+        });
+        let url = self.repo_url(&format!("/pulls/{}", number));
+        let response = self
+            .request(reqwest::Method::PATCH, url)
+            .json(&request)
+            .send()
+            .await?;
+        let updated: PullResponse = check_response(response).await?.json().await?;
+        self.decorate(number, pr).await?;
+        Ok(updated.into())
+    }
+
+    async fn post_review(
+        &self,
+        number: u64,
+        summary: &str,
+        comments: &[ReviewComment],
+    ) -> Result<(), ForgeError> {
+        let comments: Vec<serde_json::Value> = comments
+            .iter()
+            .map(|comment| {
+                let mut value = serde_json::json!({
+                    "path": comment.path,
+                    "line": comment.end_line,
+                    "side": "RIGHT",
+                    "body": comment.body,
+                });
+                if comment.start_line < comment.end_line {
+                    value["start_line"] = comment.start_line.into();
+                    value["start_side"] = "RIGHT".into();
+                }
+                value
+            })
+            .collect();
+        let url = self.repo_url(&format!("/pulls/{}/reviews", number));
+        let body = serde_json::json!({
+            "event": "COMMENT",
+            "body": summary,
+            "comments": comments,
+        });
+        let response = self.post(url, &body).send().await?;
+        check_response(response).await?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct CreatePullRequest<'a> {
+    title: &'a str,
+    body: &'a str,
+    head: &'a str,
+    base: &'a str,
+    draft: bool,
+}
+
+#[derive(Deserialize)]
+struct PullResponse {
+    number: u64,
+    html_url: String,
+    #[serde(default)]
+    title: String,
+    body: Option<String>,
+    base: Option<BranchRef>,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    #[serde(rename = "ref")]
+    branch: String,
+}
+
+#[derive(Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+impl From<PullResponse> for PullRequest {
+    fn from(pr: PullResponse) -> Self {
+        Self {
+            number: pr.number,
+            url: pr.html_url,
+            title: pr.title,
+            body: pr.body.unwrap_or_default(),
+            base: pr.base.map(|b| b.branch).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::tests::{new_pr, repo};
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn create_pull_request_with_labels_and_reviewers() {
+        let server = MockServer::start(vec![
+            (
+                201,
+                r#"{"number": 7, "html_url": "https://github.com/octo/app/pull/7"}"#,
+            ),
+            (200, "[]"),
+            (201, "{}"),
+        ]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+
+        let pr = client
+            .create_pull_request(&new_pr(&["enhancement"], &["alice", "octo/core"]))
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.url, "https://github.com/octo/app/pull/7");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/repos/octo/app/pulls");
+        assert_eq!(requests[0].header("authorization"), Some("Bearer t0k"));
+        let body = requests[0].json();
+        assert_eq!(body["head"], "feature/login");
+        assert_eq!(body["base"], "main");
+        assert_eq!(body["draft"], true);
+
+        assert_eq!(requests[1].path, "/repos/octo/app/issues/7/labels");
+        assert_eq!(requests[1].json()["labels"][0], "enhancement");
+
+        assert_eq!(
+            requests[2].path,
+            "/repos/octo/app/pulls/7/requested_reviewers"
+        );
+        assert_eq!(requests[2].json()["reviewers"][0], "alice");
+        assert_eq!(requests[2].json()["team_reviewers"][0], "core");
+    }
+
+    #[tokio::test]
+    async fn create_and_find_pull_request_from_fork() {
+        let server = MockServer::start(vec![
+            (
+                201,
+                r#"{"number": 8, "html_url": "https://github.com/octo/app/pull/8"}"#,
+            ),
+            (
+                200,
+                r#"[{"number": 8, "html_url": "https://github.com/octo/app/pull/8"}]"#,
+            ),
+        ]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+        let fork = RemoteRepo::parse("git@github.com:alice/app.git").unwrap();
+
+        let mut pr = new_pr(&[], &[]);
+        pr.head_repo = Some(fork.clone());
+        client.create_pull_request(&pr).await.unwrap();
+        let found = client
+            .find_open_pull_request("feature/login", Some(&fork))
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().number, 8);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/repos/octo/app/pulls");
+        assert_eq!(requests[0].json()["head"], "alice:feature/login");
+        assert_eq!(
+            requests[1].path,
+            "/repos/octo/app/pulls?state=open&head=alice%3Afeature%2Flogin"
+        );
+    }
+
+    #[tokio::test]
+    async fn find_and_update_pull_request() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"[{"number": 3, "html_url": "https://github.com/octo/app/pull/3",
+                    "title": "Add login", "body": null, "base": {"ref": "develop"}}]"#,
+            ),
+            (
+                200,
+                r#"{"number": 3, "html_url": "https://github.com/octo/app/pull/3"}"#,
+            ),
+        ]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+
+        let existing = client
+            .find_open_pull_request("feature/login", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(existing.number, 3);
+        assert_eq!(existing.title, "Add login");
+        assert_eq!(existing.body, "");
+        assert_eq!(existing.base, "develop");
+
+        let pr = new_pr(&[], &[]);
+        client.update_pull_request(3, &pr).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/repos/octo/app/pulls?state=open&head=octo%3Afeature%2Flogin"
+        );
+        assert_eq!(requests[1].method, "PATCH");
+        assert_eq!(requests[1].path, "/repos/octo/app/pulls/3");
+        assert_eq!(requests[1].json()["title"], "Add login");
+    }
+
+    #[tokio::test]
+    async fn post_review_with_inline_comments() {
+        let server = MockServer::start(vec![(200, r#"{"id": 1}"#)]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+
+        let comments = vec![
+            ReviewComment {
+                path: "src/lib.rs".to_string(),
+                start_line: 10,
+                end_line: 12,
+                body: "Possible overflow".to_string(),
+            },
+            ReviewComment {
+                path: "src/main.rs".to_string(),
+                start_line: 3,
+                end_line: 3,
+                body: "Unused import".to_string(),
+            },
+        ];
+        client
+            .post_review(7, "2 findings", &comments)
+            .await
+            .unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/repos/octo/app/pulls/7/reviews");
+        let body = request.json();
+        assert_eq!(body["event"], "COMMENT");
+        assert_eq!(body["comments"][0]["start_line"], 10);
+        assert_eq!(body["comments"][0]["line"], 12);
+        assert!(body["comments"][1].get("start_line").is_none());
+    }
+
+    #[tokio::test]
+    async fn current_user_returns_login() {
+        let server = MockServer::start(vec![(200, r#"{"login": "octocat", "id": 1}"#)]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+
+        let user = client.current_user().await.unwrap();
+        assert_eq!(user.as_deref(), Some("octocat"));
+        assert_eq!(server.requests()[0].path, "/user");
+    }
+
+    #[tokio::test]
+    async fn create_pull_request_reports_api_error() {
+        let server = MockServer::start(vec![(422, r#"{"message": "Validation Failed"}"#)]);
+        let client = GitHubClient::new(
+            Some(server.url.clone()),
+            "t0k".to_string(),
+            &repo("github.com", "octo", "app"),
+        );
+
+        let err = client
+            .create_pull_request(&new_pr(&["enhancement"], &["alice", "octo/core"]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ForgeError::ApiError { status: 422, .. }));
+    }
+}
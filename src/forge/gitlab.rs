@@ -0,0 +1,496 @@
+use super::{
+    base_url, check_response, Forge, ForgeError, NewPullRequest, PullRequest, RemoteRepo,
+    ReviewComment,
+};
+use serde::{Deserialize, Serialize};
+
+pub struct GitLabClient {
+    api_url: String,
+    token: String,
+    project: String,
+    client: reqwest::Client,
+}
+
+impl GitLabClient {
+    /// `host` is a hostname (`gitlab.example.com`) or a full base URL
+    /// (`http://localhost:8080`); the API lives under `/api/v4`
+    pub fn new(host: &str, token: String, remote: &RemoteRepo) -> Self {
+        Self {
+            api_url: format!("{}/api/v4", base_url(host)),
+            token,
+            project: encode_project_path(&remote.path()),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn project_url(&self, path: &str) -> String {
+        format!("{}/projects/{}{}", self.api_url, self.project, path)
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("PRIVATE-TOKEN", &self.token)
+    }
+
+    /// Look up the numeric ID of a project from its encoded path
+    async fn project_id(&self, project: &str) -> Result<u64, ForgeError> {
+        let url = format!("{}/projects/{}", self.api_url, project);
+        let response = self.request(reqwest::Method::GET, url).send().await?;
+        let found: ProjectResponse = check_response(response).await?.json().await?;
+        Ok(found.id)
+    }
+
+    /// Look up the ID of a user by username
+    async fn user_id(&self, username: &str) -> Result<Option<u64>, ForgeError> {
+        let response = self
+            .request(reqwest::Method::GET, format!("{}/users", self.api_url))
+            .query(&[("username", username.trim_start_matches('@'))])
+            .send()
+            .await?;
+        let users: Vec<UserResponse> = check_response(response).await?.json().await?;
+        Ok(users.first().map(|user| user.id))
+    }
+
+    /// Resolve usernames to user IDs, which the merge request API expects
+    async fn user_ids(&self, usernames: &[String]) -> Result<Option<Vec<u64>>, ForgeError> {
+        if usernames.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ids = Vec::new();
+        for username in usernames {
+            match self.user_id(username).await? {
+                Some(id) => ids.push(id),
+                None => {
+                    return Err(ForgeError::ApiError {
+                        status: 404,
+                        message: format!(
+                            "GitLab user not found: {}",
+                            username.trim_start_matches('@')
+                        ),
+                    })
+                }
+            }
+        }
+        Ok(Some(ids))
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GitLabClient {
+    fn describe(&self, number: u64) -> String {
+        format!("merge request !{}", number)
+    }
+
+    /// Groups can't be reviewers, so an owner like `@backend` is unknown too
+    async fn unknown_users(&self, usernames: &[String]) -> Result<Vec<String>, ForgeError> {
+        let mut unknown = Vec::new();
+        for username in usernames {
+            if self.user_id(username).await?.is_none() {
+                unknown.push(username.clone());
+            }
+        }
+        Ok(unknown)
+    }
+
+    async fn current_user(&self) -> Result<Option<String>, ForgeError> {
+        let url = format!("{}/user", self.api_url);
+        let response = self.request(reqwest::Method::GET, url).send().await?;
+        let user: CurrentUserResponse = check_response(response).await?.json().await?;
+        Ok(Some(user.username))
+    }
+
+    async fn find_open_pull_request(
+        &self,
+        head: &str,
+        head_repo: Option<&RemoteRepo>,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let source_project_id = match head_repo {
+            Some(repo) => Some(self.project_id(&encode_project_path(&repo.path())).await?),
+            None => None,
+        };
+        let response = self
+            .request(reqwest::Method::GET, self.project_url("/merge_requests"))
+            .query(&[("state", "opened"), ("source_branch", head)])
+            .send()
+            .await?;
+        let found: Vec<MergeRequestResponse> = check_response(response).await?.json().await?;
+        Ok(found
+            .into_iter()
+            .find(|mr| source_project_id.is_none() || mr.source_project_id == source_project_id)
+            .map(Into::into))
+    }
+
+    /// A merge request from a fork is created on the fork, with this
+    /// project as its target
+    async fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest, ForgeError> {
+        let reviewer_ids = self.user_ids(&pr.reviewers).await?;
+        let (url, target_project_id) = match pr.head_repo {
+            Some(ref fork) => (
+                format!(
+                    "{}/projects/{}/merge_requests",
+                    self.api_url,
+                    encode_project_path(&fork.path())
+                ),
+                Some(self.project_id(&self.project).await?),
+            ),
+            None => (self.project_url("/merge_requests"), None),
+        };
+        let title = draft_title(&pr.title, pr.draft);
+        let request = MergeRequestBody {
+            source_branch: Some(&pr.head),
+            target_branch: Some(&pr.base),
+            target_project_id,
+            title: &title,
+            description: &pr.body,
+            labels: join_labels(&pr.labels),
+            reviewer_ids,
+        };
+        let response = self
+            .request(reqwest::Method::POST, url)
+            .json(&request)
+            .send()
+            .await?;
+        let created: MergeRequestResponse = check_response(response).await?.json().await?;
+        Ok(created.into())
+    }
+
+    async fn update_pull_request(
+        &self,
+        iid: u64,
+        pr: &NewPullRequest,
+    ) -> Result<PullRequest, ForgeError> {
+        let reviewer_ids = self.user_ids(&pr.reviewers).await?;
+        let title = draft_title(&pr.title, pr.draft);
+        let request = MergeRequestBody {
+            source_branch: None,
+            target_branch: Some(&pr.base),
+            target_project_id: None,
+            title: &title,
+            description: &pr.body,
+            labels: join_labels(&pr.labels),
+            reviewer_ids,
+        };
+        let url = self.project_url(&format!("/merge_requests/{}", iid));
+        let response = self
+            .request(reqwest::Method::PUT, url)
+            .json(&request)
+            .send()
+            .await?;
+        let updated: MergeRequestResponse = check_response(response).await?.json().await?;
+        Ok(updated.into())
+    }
+
+    /// Start a discussion per comment on the merge request's latest diff,
+    /// then add the summary as a note
+    async fn post_review(
+        &self,
+        iid: u64,
+        summary: &str,
+        comments: &[ReviewComment],
+    ) -> Result<(), ForgeError> {
+        let mr_url = self.project_url(&format!("/merge_requests/{}", iid));
+        if !comments.is_empty() {
+            let response = self
+                .request(reqwest::Method::GET, mr_url.clone())
+                .send()
+                .await?;
+            let mr: MergeRequestResponse = check_response(response).await?.json().await?;
+            let refs = mr.diff_refs.ok_or_else(|| ForgeError::ApiError {
+                status: 404,
+                message: "Merge request has no diff yet".to_string(),
+            })?;
+
+            for comment in comments {
+                let response = self
+                    .request(reqwest::Method::POST, format!("{}/discussions", mr_url))
+                    .json(&serde_json::json!({
+                        "body": comment.body,
+                        "position": {
+                            "position_type": "text",
+                            "base_sha": refs.base_sha,
+                            "start_sha": refs.start_sha,
+                            "head_sha": refs.head_sha,
+                            "old_path": comment.path,
+                            "new_path": comment.path,
+                            "new_line": comment.end_line,
+                        },
+                    }))
+                    .send()
+                    .await?;
+                check_response(response).await?;
+            }
+        }
+
+        let response = self
+            .request(reqwest::Method::POST, format!("{}/notes", mr_url))
+            .json(&serde_json::json!({ "body": summary }))
+            .send()
+            .await?;
+        check_response(response).await?;
+        Ok(())
+    }
+}
+
+/// URL-encode a project path for use as a project ID (`group/app` → `group%2Fapp`)
+fn encode_project_path(path: &str) -> String {
+    path.replace('%', "%25").replace('/', "%2F")
+}
+
+fn draft_title(title: &str, draft: bool) -> String {
+    if draft && !title.starts_with("Draft:") {
+        format!("Draft: {}", title)
+    } else {
+        title.to_string()
+    }
+}
+
+fn join_labels(labels: &[String]) -> Option<String> {
+    if labels.is_empty() {
+        None
+    } else {
+        Some(labels.join(","))
+    }
+}
+
+#[derive(Serialize)]
+struct MergeRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_branch: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_branch: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_project_id: Option<u64>,
+    title: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reviewer_ids: Option<Vec<u64>>,
+}
+
+#[derive(Deserialize)]
+struct MergeRequestResponse {
+    iid: u64,
+    web_url: String,
+    #[serde(default)]
+    title: String,
+    description: Option<String>,
+    target_branch: Option<String>,
+    source_project_id: Option<u64>,
+    diff_refs: Option<DiffRefs>,
+}
+
+#[derive(Deserialize)]
+struct DiffRefs {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+}
+
+impl From<MergeRequestResponse> for PullRequest {
+    fn from(mr: MergeRequestResponse) -> Self {
+        Self {
+            number: mr.iid,
+            url: mr.web_url,
+            title: mr.title,
+            body: mr.description.unwrap_or_default(),
+            base: mr.target_branch.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProjectResponse {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct UserResponse {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct CurrentUserResponse {
+    username: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::tests::{new_pr, repo};
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn post_review_anchors_discussions_to_diff_refs() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                r#"{"iid": 9, "web_url": "u", "diff_refs": {"base_sha": "b", "start_sha": "s", "head_sha": "h"}}"#,
+            ),
+            (201, "{}"),
+            (201, "{}"),
+        ]);
+        let client = GitLabClient::new(
+            &server.url,
+            "glpat".to_string(),
+            &repo("gitlab.example.com", "group/sub", "app"),
+        );
+
+        let comments = vec![ReviewComment {
+            path: "src/app.py".to_string(),
+            start_line: 4,
+            end_line: 5,
+            body: "Missing error handling".to_string(),
+        }];
+        client.post_review(9, "1 finding", &comments).await.unwrap();
+
+        let requests = server.requests();
+        let position = &requests[1].json()["position"];
+        assert_eq!(
+            requests[1].path,
+            "/api/v4/projects/group%2Fsub%2Fapp/merge_requests/9/discussions"
+        );
+        assert_eq!(position["head_sha"], "h");
+        assert_eq!(position["new_line"], 5);
+        assert!(requests[2].path.ends_with("/merge_requests/9/notes"));
+    }
+
+    #[test]
+    fn encode_project_path_escapes_slashes() {
+        assert_eq!(encode_project_path("group/sub/app"), "group%2Fsub%2Fapp");
+    }
+
+    #[tokio::test]
+    async fn create_merge_request_resolves_reviewers() {
+        let server = MockServer::start(vec![
+            (200, r#"[{"id": 42}]"#),
+            (
+                201,
+                r#"{"iid": 5, "web_url": "https://gitlab.example.com/group/sub/app/-/merge_requests/5"}"#,
+            ),
+        ]);
+        let client = GitLabClient::new(
+            &server.url,
+            "glpat".to_string(),
+            &repo("gitlab.example.com", "group/sub", "app"),
+        );
+
+        let mr = client
+            .create_pull_request(&new_pr(&["backend", "auth"], &["@alice"]))
+            .await
+            .unwrap();
+        assert_eq!(mr.number, 5);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/api/v4/users?username=alice");
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(
+            requests[1].path,
+            "/api/v4/projects/group%2Fsub%2Fapp/merge_requests"
+        );
+        assert_eq!(requests[1].header("private-token"), Some("glpat"));
+        let body = requests[1].json();
+        assert_eq!(body["title"], "Draft: Add login");
+        assert_eq!(body["source_branch"], "feature/login");
+        assert_eq!(body["labels"], "backend,auth");
+        assert_eq!(body["reviewer_ids"][0], 42);
+    }
+
+    #[tokio::test]
+    async fn unknown_users_reports_missing_usernames() {
+        let server = MockServer::start(vec![(200, r#"[{"id": 42}]"#), (200, "[]")]);
+        let client = GitLabClient::new(
+            &server.url,
+            "glpat".to_string(),
+            &repo("gitlab.example.com", "group/sub", "app"),
+        );
+
+        let unknown = client
+            .unknown_users(&["alice".to_string(), "backend".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(unknown, vec!["backend"]);
+        assert_eq!(server.requests()[1].path, "/api/v4/users?username=backend");
+    }
+
+    #[tokio::test]
+    async fn create_and_find_merge_request_from_fork() {
+        let server = MockServer::start(vec![
+            (200, r#"{"id": 100}"#),
+            (201, r#"{"iid": 6, "web_url": "https://gl/mr/6"}"#),
+            (200, r#"{"id": 200}"#),
+            (
+                200,
+                r#"[{"iid": 4, "web_url": "https://gl/mr/4", "source_project_id": 100},
+                    {"iid": 6, "web_url": "https://gl/mr/6", "source_project_id": 200}]"#,
+            ),
+        ]);
+        let client = GitLabClient::new(
+            &server.url,
+            "glpat".to_string(),
+            &repo("gitlab.example.com", "group/sub", "app"),
+        );
+        let fork = RemoteRepo::parse("git@gitlab.example.com:alice/app.git").unwrap();
+
+        let mut pr = new_pr(&["backend", "auth"], &[]);
+        pr.head_repo = Some(fork.clone());
+        client.create_pull_request(&pr).await.unwrap();
+        let found = client
+            .find_open_pull_request("feature/login", Some(&fork))
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().number, 6);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/api/v4/projects/group%2Fsub%2Fapp");
+        assert_eq!(
+            requests[1].path,
+            "/api/v4/projects/alice%2Fapp/merge_requests"
+        );
+        assert_eq!(requests[1].json()["target_project_id"], 100);
+        assert_eq!(requests[2].path, "/api/v4/projects/alice%2Fapp");
+        assert!(requests[3]
+            .path
+            .starts_with("/api/v4/projects/group%2Fsub%2Fapp/merge_requests?"));
+    }
+
+    #[tokio::test]
+    async fn find_and_update_existing_merge_request() {
+        let server = MockServer::start(vec![
+            (200, r#"[{"iid": 9, "web_url": "https://gl/mr/9"}]"#),
+            (200, r#"{"iid": 9, "web_url": "https://gl/mr/9"}"#),
+        ]);
+        let client = GitLabClient::new(
+            &server.url,
+            "glpat".to_string(),
+            &repo("gitlab.example.com", "group/sub", "app"),
+        );
+
+        let existing = client
+            .find_open_pull_request("feature/login", None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(existing.number, 9);
+
+        let pr = new_pr(&["backend", "auth"], &[]);
+        client
+            .update_pull_request(existing.number, &pr)
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/api/v4/projects/group%2Fsub%2Fapp/merge_requests?state=opened&source_branch=feature%2Flogin"
+        );
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(
+            requests[1].path,
+            "/api/v4/projects/group%2Fsub%2Fapp/merge_requests/9"
+        );
+        assert!(requests[1].json().get("source_branch").is_none());
+    }
+}
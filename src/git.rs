@@ -1,4 +1,8 @@
-use std::process::Command;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,8 +17,96 @@ pub enum GitError {
     ExecutionError(#[from] std::io::Error),
 }
 
+/// Hash of the empty tree, used to diff root commits
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
 pub struct Git;
 
+/// Lines changed in one file, from `git diff --numstat`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl FileStat {
+    /// Parse a `--numstat` line; binary files ("-") count as zero lines
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(3, '\t');
+        let additions = parts.next()?.parse().unwrap_or(0);
+        let deletions = parts.next()?.parse().unwrap_or(0);
+        let path = parts.next()?.to_string();
+        Some(Self {
+            path,
+            additions,
+            deletions,
+        })
+    }
+}
+
+/// A commit from `git log`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author date in strict ISO 8601 format
+    pub date: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Commit {
+    /// Parse one record of the `get_commit_log` format: fields separated by
+    /// the unit separator, records by the record separator
+    fn parse(record: &str) -> Option<Self> {
+        let mut fields = record.trim_start_matches('\n').splitn(6, '\x1f');
+        let hash = fields.next()?.to_string();
+        if hash.is_empty() {
+            return None;
+        }
+        Some(Self {
+            hash,
+            author: fields.next()?.to_string(),
+            email: fields.next()?.to_string(),
+            date: fields.next()?.to_string(),
+            subject: fields.next()?.to_string(),
+            body: fields.next()?.trim().to_string(),
+        })
+    }
+
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// Test fixture: a commit with the given subject and placeholder metadata,
+/// refined with `with_hash` and `with_body`
+#[cfg(test)]
+impl Commit {
+    pub fn test(subject: &str) -> Self {
+        Self {
+            hash: "1a2b3c4d5e6f".to_string(),
+            author: "Dev".to_string(),
+            email: "dev@example.com".to_string(),
+            date: "2024-05-01T12:00:00+00:00".to_string(),
+            subject: subject.to_string(),
+            body: String::new(),
+        }
+    }
+
+    pub fn with_hash(mut self, hash: &str) -> Self {
+        self.hash = hash.to_string();
+        self
+    }
+
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+}
+
 impl Git {
     /// Check if we're in a git repository
     pub fn is_repository() -> bool {
@@ -51,9 +143,7 @@ impl Git {
 
     /// Check if there are unstaged changes
     pub fn has_unstaged_changes() -> Result<bool, GitError> {
-        let output = Command::new("git")
-            .args(["diff", "--no-color"])
-            .output()?;
+        let output = Command::new("git").args(["diff", "--no-color"]).output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed(
@@ -81,6 +171,55 @@ impl Git {
         Ok(!files.trim().is_empty())
     }
 
+    /// Get the paths of untracked files that aren't ignored
+    pub fn untracked_files() -> Result<Vec<String>, GitError> {
+        let output = Command::new("git")
+            .args(["ls-files", "--others", "--exclude-standard"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let files = String::from_utf8_lossy(&output.stdout);
+        Ok(files.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Get the diff of staged and unstaged changes against HEAD
+    pub fn get_worktree_diff() -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args(["diff", "HEAD", "--no-color"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Stash local changes with the given message
+    pub fn stash_push(message: &str, include_untracked: bool) -> Result<(), GitError> {
+        let mut cmd = Command::new("git");
+        cmd.args(["stash", "push", "-m", message]);
+        if include_untracked {
+            cmd.arg("--include-untracked");
+        }
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Stage all changes
     pub fn stage_all() -> Result<(), GitError> {
         let output = Command::new("git").args(["add", "-A"]).output()?;
@@ -113,15 +252,108 @@ impl Git {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    /// Get commit log between base and HEAD
-    pub fn get_commit_log(base: &str) -> Result<Vec<String>, GitError> {
+    /// Get the paths of files changed between the base branch and HEAD
+    pub fn get_changed_files(base: &str) -> Result<Vec<String>, GitError> {
+        let output = Command::new("git")
+            .args(["diff", "--name-only", &format!("{}...HEAD", base)])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let files = String::from_utf8_lossy(&output.stdout);
+        Ok(files.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Get per-file added and deleted line counts between the base branch and HEAD
+    pub fn get_diff_stats(base: &str) -> Result<Vec<FileStat>, GitError> {
+        let output = Command::new("git")
+            .args([
+                "diff",
+                "--numstat",
+                "--no-renames",
+                &format!("{}...HEAD", base),
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let stats = String::from_utf8_lossy(&output.stdout);
+        Ok(stats.lines().filter_map(FileStat::parse).collect())
+    }
+
+    /// Get the commits reachable from `to` but not from `from`, newest
+    /// first. Without `from`, the whole history of `to` is returned.
+    pub fn get_commit_log(from: Option<&str>, to: &str) -> Result<Vec<Commit>, GitError> {
+        Self::commit_log(from, to, false)
+    }
+
+    /// Like `get_commit_log`, without merge commits, whose subjects
+    /// ("Merge pull request #12...") describe no change of their own
+    pub fn get_commit_log_without_merges(
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Vec<Commit>, GitError> {
+        Self::commit_log(from, to, true)
+    }
+
+    fn commit_log(from: Option<&str>, to: &str, no_merges: bool) -> Result<Vec<Commit>, GitError> {
+        let range = match from {
+            Some(from) => format!("{}..{}", from, to),
+            None => to.to_string(),
+        };
+        let mut command = Command::new("git");
+        command.args([
+            "log",
+            &range,
+            "--pretty=format:%H%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1e",
+            "--no-color",
+        ]);
+        if no_merges {
+            command.arg("--no-merges");
+        }
+        let output = command.output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let log = String::from_utf8_lossy(&output.stdout);
+        Ok(log.split('\x1e').filter_map(Commit::parse).collect())
+    }
+
+    /// Get up to `max_count` of the commits selected by `revs` (e.g.
+    /// `["HEAD", "^v1.0"]`), after skipping the first `skip`, with their
+    /// diffs without context lines, newest first. Each diff is cut after
+    /// `max_patch_bytes`.
+    pub fn get_commit_patches(
+        revs: &[&str],
+        skip: usize,
+        max_count: usize,
+        max_patch_bytes: usize,
+    ) -> Result<Vec<(Commit, String)>, GitError> {
         let output = Command::new("git")
             .args([
                 "log",
-                &format!("{}..HEAD", base),
-                "--pretty=format:%s",
+                "--pretty=format:%x1e%H%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1f",
+                "--patch",
+                "--unified=0",
                 "--no-color",
+                "--no-ext-diff",
+                &format!("--skip={}", skip),
+                &format!("--max-count={}", max_count),
             ])
+            .args(revs)
+            .arg("--")
             .output()?;
 
         if !output.status.success() {
@@ -131,7 +363,231 @@ impl Git {
         }
 
         let log = String::from_utf8_lossy(&output.stdout);
-        Ok(log.lines().map(|s| s.to_string()).collect())
+        Ok(log
+            .split('\x1e')
+            .filter_map(|record| {
+                // The patch follows the sixth field separator
+                let (split, _) = record.match_indices('\x1f').nth(5)?;
+                let commit = Commit::parse(&record[..split])?;
+                let patch = &record[split + 1..];
+                let mut end = patch.len().min(max_patch_bytes);
+                while !patch.is_char_boundary(end) {
+                    end -= 1;
+                }
+                Some((commit, patch[..end].to_string()))
+            })
+            .collect())
+    }
+
+    /// Get the commits on local branches of the repository at `repo` since
+    /// `since` (any date git understands, e.g. "yesterday"), newest first,
+    /// each with the branch it was reached from
+    pub fn get_activity(
+        repo: &Path,
+        since: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<(String, Commit)>, GitError> {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(repo).args([
+            "log",
+            "--branches",
+            "--source",
+            &format!("--since={}", since),
+            "--pretty=format:%S%x1f%H%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1e",
+            "--no-color",
+        ]);
+        if let Some(author) = author {
+            command.arg(format!("--author={}", author));
+        }
+        let output = command.output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let log = String::from_utf8_lossy(&output.stdout);
+        Ok(log
+            .split('\x1e')
+            .filter_map(|record| {
+                let (branch, record) = record.trim_start_matches('\n').split_once('\x1f')?;
+                Some((branch.to_string(), Commit::parse(record)?))
+            })
+            .collect())
+    }
+
+    /// Get the configured `user.email` of the repository at `repo`
+    pub fn user_email(repo: &Path) -> Result<String, GitError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["config", "user.email"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed("user.email is not set".to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Get the hashes of all commits reachable from `rev`
+    pub fn rev_list(rev: &str) -> Result<Vec<String>, GitError> {
+        let output = Command::new("git").args(["rev-list", rev, "--"]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let hashes = String::from_utf8_lossy(&output.stdout);
+        Ok(hashes.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Resolve a revision to a full commit hash
+    pub fn rev_parse(rev: &str) -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{}^{{commit}}", rev),
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(format!(
+                "Unknown revision: {}",
+                rev
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Get the best common ancestor of two revisions
+    pub fn merge_base(a: &str, b: &str) -> Result<String, GitError> {
+        let output = Command::new("git").args(["merge-base", a, b]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(format!(
+                "No common ancestor of {} and {}",
+                a, b
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Check if `ancestor` is reachable from `rev`
+    pub fn is_ancestor(ancestor: &str, rev: &str) -> bool {
+        Command::new("git")
+            .args(["merge-base", "--is-ancestor", ancestor, rev])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Check if the commit `hash` is in the object database
+    pub fn has_commit(hash: &str) -> bool {
+        Command::new("git")
+            .args(["cat-file", "-e", &format!("{}^{{commit}}", hash)])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Check if `rev` is reachable from any branch, tag or other ref
+    pub fn is_referenced(rev: &str) -> bool {
+        Command::new("git")
+            .args([
+                "for-each-ref",
+                "--count=1",
+                "--format=%(refname)",
+                "--contains",
+                rev,
+            ])
+            .output()
+            .map(|o| o.status.success() && !String::from_utf8_lossy(&o.stdout).trim().is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Get the most recent tag reachable from `rev`
+    pub fn last_tag(rev: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["describe", "--tags", "--abbrev=0", rev])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!tag.is_empty()).then_some(tag)
+    }
+
+    /// Show the message and diff of a commit, or of each commit in a range
+    pub fn show(rev: &str) -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args(["show", "--no-color", "--patch-with-stat", rev, "--"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Get the tags reachable from `rev`
+    pub fn tags(rev: &str) -> Result<Vec<String>, GitError> {
+        let output = Command::new("git")
+            .args(["tag", "--list", "--merged", rev])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let tags = String::from_utf8_lossy(&output.stdout);
+        Ok(tags.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Check if a tag exists
+    pub fn has_tag(name: &str) -> bool {
+        Command::new("git")
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/tags/{}", name),
+            ])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Create an annotated tag on HEAD. The message is kept verbatim, so
+    /// markdown headings aren't stripped as comments.
+    pub fn create_tag(name: &str, message: &str) -> Result<(), GitError> {
+        let output = Command::new("git")
+            .args(["tag", "-a", name, "--cleanup=verbatim", "-m", message])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
     /// Get current branch name
@@ -152,17 +608,8 @@ impl Git {
     /// Get default branch (main or master)
     pub fn default_branch() -> Result<String, GitError> {
         // Try to get the default branch from remote
-        let output = Command::new("git")
-            .args(["symbolic-ref", "refs/remotes/origin/HEAD", "--short"])
-            .output()?;
-
-        if output.status.success() {
-            let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            // Remove "origin/" prefix
-            return Ok(branch
-                .strip_prefix("origin/")
-                .unwrap_or(&branch)
-                .to_string());
+        if let Some(branch) = Self::remote_default_branch("origin") {
+            return Ok(branch);
         }
 
         // Fallback: check if main or master exists
@@ -180,6 +627,82 @@ impl Git {
         ))
     }
 
+    /// Get the default branch of a remote from its HEAD ref, if known
+    pub fn remote_default_branch(remote: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args([
+                "symbolic-ref",
+                &format!("refs/remotes/{}/HEAD", remote),
+                "--short",
+            ])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        // Remove the "origin/" prefix
+        let prefix = format!("{}/", remote);
+        Some(branch.strip_prefix(&prefix).unwrap_or(&branch).to_string())
+    }
+
+    /// Check if a remote with the given name exists
+    pub fn has_remote(remote: &str) -> bool {
+        Command::new("git")
+            .args(["remote", "get-url", remote])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Check if a remote-tracking branch such as `origin/main` exists
+    pub fn has_remote_branch(name: &str) -> bool {
+        Command::new("git")
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/remotes/{}", name),
+            ])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Fetch a branch from a remote, updating its remote-tracking branch
+    pub fn fetch(remote: &str, branch: &str) -> Result<(), GitError> {
+        let output = Command::new("git")
+            .args(["fetch", "--quiet", remote, branch])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// When a remote-tracking branch was last fetched, judging by the
+    /// modification times of FETCH_HEAD and the branch's reflog
+    pub fn last_fetched(name: &str) -> Option<SystemTime> {
+        let reflog = format!("logs/refs/remotes/{}", name);
+        ["FETCH_HEAD", reflog.as_str()]
+            .iter()
+            .filter_map(|path| {
+                let output = Command::new("git")
+                    .args(["rev-parse", "--git-path", path])
+                    .output()
+                    .ok()?;
+                let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                std::fs::metadata(path).ok()?.modified().ok()
+            })
+            .max()
+    }
+
     /// Create a commit with the given message
     pub fn commit(message: &str) -> Result<(), GitError> {
         let output = Command::new("git")
@@ -194,4 +717,387 @@ impl Git {
 
         Ok(())
     }
+
+    /// Get the staged changes as a patch that `git apply` accepts, whatever
+    /// the user's diff settings: binary changes included, renames as a
+    /// deletion and an addition
+    pub fn get_staged_patch() -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args([
+                "diff",
+                "--cached",
+                "--binary",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-renames",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let diff = String::from_utf8_lossy(&output.stdout).to_string();
+        if diff.trim().is_empty() {
+            return Err(GitError::NoStagedChanges);
+        }
+
+        Ok(diff)
+    }
+
+    /// Apply a patch to the index only
+    pub fn apply_cached(patch: &str) -> Result<(), GitError> {
+        let mut child = Command::new("git")
+            .args(["apply", "--cached", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(patch.as_bytes())?;
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Write the index as a tree object and return its hash
+    pub fn write_tree() -> Result<String, GitError> {
+        let output = Command::new("git").args(["write-tree"]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Replace the index with a tree, leaving the working tree alone
+    pub fn read_tree(tree: &str) -> Result<(), GitError> {
+        let output = Command::new("git").args(["read-tree", tree]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Move HEAD to `rev`, keeping the index and the working tree
+    pub fn reset_soft(rev: &str) -> Result<(), GitError> {
+        let output = Command::new("git")
+            .args(["reset", "--soft", rev])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the editor git uses for commit messages (GIT_EDITOR, core.editor, ...)
+    pub fn editor() -> Result<String, GitError> {
+        let output = Command::new("git").args(["var", "GIT_EDITOR"]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Get the paths of files with unresolved conflicts, relative to the repository root
+    pub fn conflicted_files() -> Result<Vec<String>, GitError> {
+        let output = Command::new("git")
+            .args(["diff", "--name-only", "--diff-filter=U"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let files = String::from_utf8_lossy(&output.stdout);
+        let mut files: Vec<String> = files.lines().map(|s| s.to_string()).collect();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Get a conflicted file's content at an index stage: 1 for the common
+    /// ancestor, 2 for ours, 3 for theirs. None if the side has no such file.
+    pub fn show_stage(stage: u8, path: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["show", &format!(":{}:{}", stage, path)])
+            .output()
+            .ok()?;
+
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Stage a file, e.g. to mark its conflicts as resolved
+    pub fn stage_file(path: &str) -> Result<(), GitError> {
+        let output = Command::new("git").args(["add", "--", path]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check if a local branch exists
+    pub fn has_branch(name: &str) -> bool {
+        Command::new("git")
+            .args([
+                "show-ref",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{}", name),
+            ])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Create a branch at HEAD and switch to it, keeping local changes
+    pub fn switch_create(name: &str) -> Result<(), GitError> {
+        let output = Command::new("git").args(["switch", "-c", name]).output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check if HEAD points to a commit
+    pub fn has_head() -> bool {
+        Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Check if HEAD is contained in any remote-tracking branch
+    pub fn is_head_pushed() -> Result<bool, GitError> {
+        let output = Command::new("git")
+            .args(["branch", "--remotes", "--contains", "HEAD"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+    }
+
+    /// Get the diff of HEAD's parent against the index, i.e. the changes
+    /// of the last commit plus anything staged since
+    pub fn get_amend_diff() -> Result<String, GitError> {
+        if !Self::is_repository() {
+            return Err(GitError::NotARepository);
+        }
+
+        // A root commit has no parent, so compare against the empty tree
+        let parent_output = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", "HEAD~1"])
+            .output()?;
+        let parent = if parent_output.status.success() {
+            "HEAD~1"
+        } else {
+            EMPTY_TREE
+        };
+
+        let output = Command::new("git")
+            .args(["diff", "--cached", "--no-color", parent])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let diff = String::from_utf8_lossy(&output.stdout).to_string();
+        if diff.trim().is_empty() {
+            return Err(GitError::NoStagedChanges);
+        }
+
+        Ok(diff)
+    }
+
+    /// Get the full message of the last commit
+    pub fn last_commit_message() -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args(["log", "-1", "--pretty=format:%B", "--no-color"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Amend the last commit with the given message, including staged changes
+    pub fn commit_amend(message: &str) -> Result<(), GitError> {
+        let output = Command::new("git")
+            .args(["commit", "--amend", "-m", message])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the hooks directory, honoring core.hooksPath
+    pub fn hooks_dir() -> Result<PathBuf, GitError> {
+        if !Self::is_repository() {
+            return Err(GitError::NotARepository);
+        }
+
+        let output = Command::new("git")
+            .args(["rev-parse", "--git-path", "hooks"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Get the `.git` directory shared by all worktrees
+    pub fn common_dir() -> Result<PathBuf, GitError> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::NotARepository);
+        }
+
+        Ok(PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Get the top-level directory of the working tree
+    pub fn repo_root() -> Result<PathBuf, GitError> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::NotARepository);
+        }
+
+        Ok(PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Path of the current directory relative to the repository root,
+    /// with a trailing slash (empty at the root)
+    pub fn show_prefix() -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-prefix"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::NotARepository);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Get the URL of a remote
+    pub fn remote_url(remote: &str) -> Result<String, GitError> {
+        let output = Command::new("git")
+            .args(["remote", "get-url", remote])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Check if the current branch has no upstream or has unpushed commits
+    pub fn needs_push() -> Result<bool, GitError> {
+        let output = Command::new("git")
+            .args(["rev-list", "--count", "@{upstream}..HEAD"])
+            .output()?;
+
+        if !output.status.success() {
+            // No upstream configured
+            return Ok(true);
+        }
+
+        let ahead: u64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        Ok(ahead > 0)
+    }
+
+    /// Push a branch and set it as upstream
+    pub fn push_branch(remote: &str, branch: &str) -> Result<(), GitError> {
+        let output = Command::new("git")
+            .args(["push", "--set-upstream", remote, branch])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
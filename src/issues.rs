@@ -0,0 +1,214 @@
+//! Issue and ticket keys referenced by branch names and commit messages
+
+use crate::config::{IssueStyle, IssuesConfig};
+use crate::git::Git;
+use regex::Regex;
+
+/// Compile the configured issue key patterns
+pub fn compile(patterns: &[String]) -> Result<Vec<Regex>, regex::Error> {
+    patterns.iter().map(|pattern| Regex::new(pattern)).collect()
+}
+
+/// The configured patterns plus one for the keys of the configured projects
+pub fn patterns(config: &IssuesConfig) -> Result<Vec<Regex>, regex::Error> {
+    let mut patterns = compile(&config.patterns)?;
+    if !config.project_keys.is_empty() {
+        let keys: Vec<String> = config
+            .project_keys
+            .iter()
+            .map(|key| regex::escape(key))
+            .collect();
+        patterns.push(Regex::new(&format!(r"\b(?:{})-\d+\b", keys.join("|")))?);
+    }
+    Ok(patterns)
+}
+
+/// Issue keys referenced by a branch
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IssueKeys {
+    /// Keys in the branch name: the issues the branch is for
+    pub branch: Vec<String>,
+    /// Other keys mentioned by the commits since the base
+    pub commits: Vec<String>,
+}
+
+/// Issue keys in the current branch name and the commits since `base`,
+/// in order of appearance
+pub fn detect(config: &IssuesConfig, base: Option<&str>) -> Result<IssueKeys, regex::Error> {
+    let patterns = patterns(config)?;
+
+    let branch = match Git::current_branch() {
+        Ok(branch) => extract(&[branch], &patterns),
+        Err(_) => Vec::new(),
+    };
+    let subjects: Vec<String> = match base {
+        Some(base) => Git::get_commit_log(Some(base), "HEAD")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|commit| commit.subject)
+            .collect(),
+        None => Vec::new(),
+    };
+    let commits = extract(&subjects, &patterns)
+        .into_iter()
+        .filter(|key| !branch.contains(key))
+        .collect();
+
+    Ok(IssueKeys { branch, commits })
+}
+
+/// Find issue keys in `texts` without duplicates. A pattern's first capture
+/// group is used as the key when it has one, otherwise the whole match.
+pub fn extract(texts: &[String], patterns: &[Regex]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for text in texts {
+        let mut found: Vec<(usize, &str)> = patterns
+            .iter()
+            .flat_map(|pattern| pattern.captures_iter(text))
+            .filter_map(|captures| captures.get(1).or_else(|| captures.get(0)))
+            .map(|m| (m.start(), m.as_str()))
+            .collect();
+        found.sort_by_key(|(start, _)| *start);
+
+        for (_, key) in found {
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.to_string());
+            }
+        }
+    }
+    keys
+}
+
+/// Add the keys a commit message doesn't mention yet, in the configured style
+pub fn apply_to_commit(message: &str, keys: &[String], style: IssueStyle) -> String {
+    let missing: Vec<&str> = missing_keys(message, keys);
+    if missing.is_empty() {
+        return message.to_string();
+    }
+    match style {
+        IssueStyle::Footer => format!("{}\n\nRefs: {}", message.trim_end(), missing.join(", ")),
+        IssueStyle::Prefix => format!("{} {}", missing.join(" "), message),
+        IssueStyle::Off => message.to_string(),
+    }
+}
+
+/// Append a closing line (e.g. "Closes PROJ-123") for each key of the branch
+/// and a `Refs:` line for the keys of its commits that the body doesn't mention.
+/// Keys from commits never close issues: they are often references like "see #5".
+pub fn apply_to_pr_body(body: &str, keys: &IssueKeys, keyword: &str) -> String {
+    let mut lines: Vec<String> = missing_keys(body, &keys.branch)
+        .iter()
+        .map(|key| format!("{} {}", keyword, key))
+        .collect();
+    let related = missing_keys(body, &keys.commits);
+    if !related.is_empty() {
+        lines.push(format!("Refs: {}", related.join(", ")));
+    }
+    if lines.is_empty() {
+        return body.to_string();
+    }
+    if body.trim().is_empty() {
+        lines.join("\n")
+    } else {
+        format!("{}\n\n{}", body.trim_end(), lines.join("\n"))
+    }
+}
+
+/// Keys that `text` doesn't mention as a whole word, so `#23` doesn't count
+/// as a mention of `#2`
+fn missing_keys<'a>(text: &str, keys: &'a [String]) -> Vec<&'a str> {
+    keys.iter()
+        .map(String::as_str)
+        .filter(|key| {
+            let mention = format!(r"(?:^|\W){}(?:\W|$)", regex::escape(key));
+            !Regex::new(&mention).is_ok_and(|re| re.is_match(text))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_from_branch_and_commits() {
+        let config = IssuesConfig {
+            project_keys: texts(&["PROJ", "OPS"]),
+            ..IssuesConfig::default()
+        };
+        let keys = extract(
+            &texts(&[
+                "feature/PROJ-1234-add-login",
+                "fix: handle expired tokens (#88)",
+                "PROJ-1234 follow-up, see !45",
+                "fix: handle UTF-8 paths in OPS-7",
+            ]),
+            &patterns(&config).unwrap(),
+        );
+        assert_eq!(keys, vec!["PROJ-1234", "#88", "!45", "OPS-7"]);
+    }
+
+    #[test]
+    fn default_patterns_skip_standard_names() {
+        let patterns = patterns(&IssuesConfig::default()).unwrap();
+        let keys = extract(
+            &texts(&["fix: UTF-8 paths, SHA-256 sums and ISO-8601 dates (#12)"]),
+            &patterns,
+        );
+        assert_eq!(keys, vec!["#12"]);
+    }
+
+    #[test]
+    fn extract_uses_capture_group() {
+        let patterns = compile(&[r"(?:^|/)(\d+)-".to_string(), r"!\d+".to_string()]).unwrap();
+        let keys = extract(&texts(&["42-fix-crash", "see !45"]), &patterns);
+        assert_eq!(keys, vec!["42", "!45"]);
+    }
+
+    #[test]
+    fn apply_to_commit_styles() {
+        let keys = texts(&["PROJ-1", "#2"]);
+        assert_eq!(
+            apply_to_commit("feat: add login", &keys, IssueStyle::Footer),
+            "feat: add login\n\nRefs: PROJ-1, #2"
+        );
+        assert_eq!(
+            apply_to_commit("feat: add login (#2)", &keys, IssueStyle::Prefix),
+            "PROJ-1 feat: add login (#2)"
+        );
+        assert_eq!(
+            apply_to_commit("feat: add login", &keys, IssueStyle::Off),
+            "feat: add login"
+        );
+    }
+
+    #[test]
+    fn apply_to_pr_body_closes_branch_keys_only() {
+        let keys = IssueKeys {
+            branch: texts(&["PROJ-1", "#2"]),
+            commits: texts(&["#41", "#5"]),
+        };
+        assert_eq!(
+            apply_to_pr_body("## Summary\nFixes #2, see #5.\n", &keys, "Closes"),
+            "## Summary\nFixes #2, see #5.\n\nCloses PROJ-1\nRefs: #41"
+        );
+        assert_eq!(
+            apply_to_pr_body("", &keys, "Fixes"),
+            "Fixes PROJ-1\nFixes #2\nRefs: #41, #5"
+        );
+    }
+
+    #[test]
+    fn missing_keys_match_whole_keys() {
+        let keys = texts(&["#2", "PROJ-1"]);
+        assert_eq!(
+            missing_keys("fix: see #23 and PROJ-12", &keys),
+            vec!["#2", "PROJ-1"]
+        );
+        assert!(missing_keys("(#2) PROJ-1: done", &keys).is_empty());
+    }
+}
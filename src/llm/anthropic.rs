@@ -1,17 +1,20 @@
-use super::{LlmError, LlmProvider};
+use super::{read_lines, ChatMessage, Completion, LlmError, LlmProvider, OnText, Usage};
+use crate::config::GenerationConfig;
 use serde::{Deserialize, Serialize};
 
 pub struct AnthropicClient {
     api_key: String,
     model: String,
+    params: GenerationConfig,
     client: reqwest::Client,
 }
 
 impl AnthropicClient {
-    pub fn new(api_key: String, model: String) -> Self {
+    pub fn new(api_key: String, model: String, params: GenerationConfig) -> Self {
         Self {
             api_key,
             model,
+            params,
             client: reqwest::Client::new(),
         }
     }
@@ -22,6 +25,12 @@ struct MessageRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -33,6 +42,13 @@ struct Message {
 #[derive(Deserialize)]
 struct MessageResponse {
     content: Vec<ContentBlock>,
+    usage: Option<MessageUsage>,
+}
+
+#[derive(Deserialize)]
+struct MessageUsage {
+    input_tokens: u32,
+    output_tokens: u32,
 }
 
 #[derive(Deserialize)]
@@ -40,25 +56,72 @@ struct ContentBlock {
     text: String,
 }
 
-#[async_trait::async_trait]
-impl LlmProvider for AnthropicClient {
-    async fn generate(&self, prompt: &str) -> Result<String, LlmError> {
-        let request = MessageRequest {
+/// Data of one server-sent event of a streamed response
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: StreamMessage,
+    },
+    ContentBlockDelta {
+        delta: TextDelta,
+    },
+    MessageDelta {
+        usage: Option<DeltaUsage>,
+    },
+    Error {
+        error: StreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StreamMessage {
+    usage: Option<MessageUsage>,
+}
+
+#[derive(Deserialize)]
+struct TextDelta {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DeltaUsage {
+    output_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct StreamError {
+    message: String,
+}
+
+impl AnthropicClient {
+    fn request(&self, messages: &[ChatMessage], stream: bool) -> MessageRequest {
+        MessageRequest {
             model: self.model.clone(),
-            max_tokens: 1024,
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-        };
+            max_tokens: self.params.max_tokens.unwrap_or(1024),
+            messages: messages
+                .iter()
+                .map(|m| Message {
+                    role: m.role.to_string(),
+                    content: m.content.clone(),
+                })
+                .collect(),
+            temperature: self.params.temperature,
+            top_p: self.params.top_p,
+            stream,
+        }
+    }
 
+    async fn send(&self, request: &MessageRequest) -> Result<reqwest::Response, LlmError> {
         let response = self
             .client
             .post("https://api.anthropic.com/v1/messages")
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(request)
             .send()
             .await?;
 
@@ -66,6 +129,15 @@ impl LlmProvider for AnthropicClient {
             let error_text = response.text().await.unwrap_or_default();
             return Err(LlmError::ApiError(error_text));
         }
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for AnthropicClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion, LlmError> {
+        let request = self.request(&[ChatMessage::user(prompt)], false);
+        let response = self.send(&request).await?;
 
         let msg_response: MessageResponse = response.json().await?;
         let content = msg_response
@@ -74,6 +146,55 @@ impl LlmProvider for AnthropicClient {
             .map(|c| c.text.clone())
             .unwrap_or_default();
 
-        Ok(content)
+        Ok(Completion {
+            text: content,
+            usage: msg_response.usage.map(|u| Usage {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+            }),
+        })
+    }
+
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        on_text: OnText<'_>,
+    ) -> Result<Completion, LlmError> {
+        let response = self.send(&self.request(messages, true)).await?;
+
+        let mut text = String::new();
+        let mut usage = Usage::default();
+        read_lines(response, |line| {
+            let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                return Ok(());
+            };
+            let event: StreamEvent = serde_json::from_str(data)
+                .map_err(|e| LlmError::ApiError(format!("invalid stream event: {}", e)))?;
+            match event {
+                StreamEvent::MessageStart { message } => {
+                    if let Some(u) = message.usage {
+                        usage.input_tokens = u.input_tokens;
+                    }
+                }
+                StreamEvent::ContentBlockDelta { delta } => {
+                    if let Some(content) = delta.text {
+                        on_text(&content);
+                        text.push_str(&content);
+                    }
+                }
+                StreamEvent::MessageDelta { usage: Some(u) } => {
+                    usage.output_tokens = u.output_tokens;
+                }
+                StreamEvent::Error { error } => return Err(LlmError::ApiError(error.message)),
+                StreamEvent::MessageDelta { usage: None } | StreamEvent::Other => {}
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(Completion {
+            text,
+            usage: Some(usage),
+        })
     }
 }
@@ -3,6 +3,7 @@ mod ollama;
 mod openai;
 
 use crate::config::Config;
+use serde::Serialize;
 use thiserror::Error;
 
 pub use anthropic::AnthropicClient;
@@ -19,15 +20,93 @@ pub enum LlmError {
     MissingApiKey(String),
     #[error("Unknown provider: {0}")]
     UnknownProvider(String),
+    #[error("{0} are not supported by this provider")]
+    Unsupported(String),
 }
 
+/// Token counts reported by the provider
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+/// Generated text along with the provider's token usage, if reported
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub text: String,
+    pub usage: Option<Usage>,
+}
+
+/// Author of a message in a conversation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::Assistant => write!(f, "assistant"),
+        }
+    }
+}
+
+/// A message in a multi-turn conversation
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub role: Role,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// Callback receiving the text of a streamed reply as it arrives
+pub type OnText<'a> = &'a mut (dyn FnMut(&str) + Send);
+
 #[async_trait::async_trait]
 pub trait LlmProvider: Send + Sync {
-    async fn generate(&self, prompt: &str) -> Result<String, LlmError>;
+    async fn generate(&self, prompt: &str) -> Result<Completion, LlmError>;
+
+    /// Reply to a conversation, streaming the reply to `on_text`
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        on_text: OnText<'_>,
+    ) -> Result<Completion, LlmError>;
+
+    /// Embedding model used when none is configured
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Embed each text as a vector for similarity search
+    async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        let _ = (texts, model);
+        Err(LlmError::Unsupported("Embeddings".to_string()))
+    }
 }
 
 pub struct LlmClient {
     provider: Box<dyn LlmProvider>,
+    name: String,
+    model: String,
 }
 
 impl LlmClient {
@@ -44,6 +123,7 @@ impl LlmClient {
                     api_key,
                     config.provider.model.clone(),
                     config.provider.base_url.clone(),
+                    config.generation.clone(),
                 ))
             }
             "anthropic" => {
@@ -53,19 +133,76 @@ impl LlmClient {
                     .clone()
                     .or_else(|| std::env::var("ANTHROPIC_API_KEY").ok())
                     .ok_or_else(|| LlmError::MissingApiKey("Anthropic".to_string()))?;
-                Box::new(AnthropicClient::new(api_key, config.provider.model.clone()))
+                Box::new(AnthropicClient::new(
+                    api_key,
+                    config.provider.model.clone(),
+                    config.generation.clone(),
+                ))
             }
             "ollama" => Box::new(OllamaClient::new(
                 config.provider.ollama_url.clone(),
                 config.provider.model.clone(),
+                config.generation.clone(),
             )),
             other => return Err(LlmError::UnknownProvider(other.to_string())),
         };
 
-        Ok(Self { provider })
+        Ok(Self {
+            provider,
+            name: config.provider.name.clone(),
+            model: config.provider.model.clone(),
+        })
     }
 
-    pub async fn generate(&self, prompt: &str) -> Result<String, LlmError> {
+    pub async fn generate(&self, prompt: &str) -> Result<Completion, LlmError> {
         self.provider.generate(prompt).await
     }
+
+    pub async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        on_text: OnText<'_>,
+    ) -> Result<Completion, LlmError> {
+        self.provider.chat(messages, on_text).await
+    }
+
+    /// The configured embedding model, or the provider's default
+    pub fn embedding_model(&self, configured: Option<&str>) -> Option<String> {
+        configured
+            .or_else(|| self.provider.default_embedding_model())
+            .map(str::to_string)
+    }
+
+    pub async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        self.provider.embed(texts, model).await
+    }
+
+    /// Provider name, e.g. "openai"
+    pub fn provider_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+/// Read a streamed response line by line, for server-sent events and
+/// newline-delimited JSON
+async fn read_lines(
+    mut response: reqwest::Response,
+    mut on_line: impl FnMut(&str) -> Result<(), LlmError> + Send,
+) -> Result<(), LlmError> {
+    let mut buffer: Vec<u8> = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        buffer.extend_from_slice(&chunk);
+        while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            on_line(String::from_utf8_lossy(&line).trim_end())?;
+        }
+    }
+    if !buffer.is_empty() {
+        on_line(String::from_utf8_lossy(&buffer).trim_end())?;
+    }
+    Ok(())
 }
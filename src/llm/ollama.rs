@@ -1,20 +1,45 @@
-use super::{LlmError, LlmProvider};
+use super::{read_lines, ChatMessage, Completion, LlmError, LlmProvider, OnText, Usage};
+use crate::config::GenerationConfig;
 use serde::{Deserialize, Serialize};
 
+const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
 pub struct OllamaClient {
     base_url: String,
     model: String,
+    params: GenerationConfig,
     client: reqwest::Client,
 }
 
 impl OllamaClient {
-    pub fn new(base_url: String, model: String) -> Self {
+    pub fn new(base_url: String, model: String, params: GenerationConfig) -> Self {
         Self {
             base_url,
             model,
+            params,
             client: reqwest::Client::new(),
         }
     }
+
+    fn options(&self) -> GenerateOptions {
+        GenerateOptions {
+            temperature: self.params.temperature,
+            top_p: self.params.top_p,
+            num_predict: self.params.max_tokens,
+            seed: self.params.seed,
+        }
+    }
+}
+
+/// Ollama reports token counts as prompt and generated "eval" counts
+fn usage(prompt_eval_count: Option<u32>, eval_count: Option<u32>) -> Option<Usage> {
+    match (prompt_eval_count, eval_count) {
+        (None, None) => None,
+        (input, output) => Some(Usage {
+            input_tokens: input.unwrap_or(0),
+            output_tokens: output.unwrap_or(0),
+        }),
+    }
 }
 
 #[derive(Serialize)]
@@ -22,20 +47,75 @@ struct GenerateRequest {
     model: String,
     prompt: String,
     stream: bool,
+    options: GenerateOptions,
+}
+
+#[derive(Serialize)]
+struct GenerateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    stream: bool,
+    options: GenerateOptions,
+}
+
+#[derive(Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+/// One line of a streamed `/api/chat` response
+#[derive(Deserialize)]
+struct ChatChunk {
+    message: Option<ChunkMessage>,
+    error: Option<String>,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ChunkMessage {
+    content: String,
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
 }
 
 #[derive(Deserialize)]
 struct GenerateResponse {
     response: String,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
 }
 
 #[async_trait::async_trait]
 impl LlmProvider for OllamaClient {
-    async fn generate(&self, prompt: &str) -> Result<String, LlmError> {
+    async fn generate(&self, prompt: &str) -> Result<Completion, LlmError> {
         let request = GenerateRequest {
             model: self.model.clone(),
             prompt: prompt.to_string(),
             stream: false,
+            options: self.options(),
         };
 
         let url = format!("{}/api/generate", self.base_url);
@@ -47,6 +127,123 @@ impl LlmProvider for OllamaClient {
         }
 
         let gen_response: GenerateResponse = response.json().await?;
-        Ok(gen_response.response)
+        Ok(Completion {
+            text: gen_response.response,
+            usage: usage(gen_response.prompt_eval_count, gen_response.eval_count),
+        })
+    }
+
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        on_text: OnText<'_>,
+    ) -> Result<Completion, LlmError> {
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: messages
+                .iter()
+                .map(|m| Message {
+                    role: m.role.to_string(),
+                    content: m.content.clone(),
+                })
+                .collect(),
+            stream: true,
+            options: self.options(),
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::ApiError(error_text));
+        }
+
+        let mut text = String::new();
+        let mut counts = (None, None);
+        read_lines(response, |line| {
+            if line.is_empty() {
+                return Ok(());
+            }
+            let chunk: ChatChunk = serde_json::from_str(line)
+                .map_err(|e| LlmError::ApiError(format!("invalid stream chunk: {}", e)))?;
+            if let Some(error) = chunk.error {
+                return Err(LlmError::ApiError(error));
+            }
+            if let Some(message) = chunk.message {
+                on_text(&message.content);
+                text.push_str(&message.content);
+            }
+            if chunk.eval_count.is_some() {
+                counts = (chunk.prompt_eval_count, chunk.eval_count);
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(Completion {
+            text,
+            usage: usage(counts.0, counts.1),
+        })
+    }
+
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        Some(DEFAULT_EMBEDDING_MODEL)
+    }
+
+    async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        let url = format!("{}/api/embed", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&EmbedRequest {
+                model,
+                input: texts,
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::ApiError(error_text));
+        }
+
+        let embed_response: EmbedResponse = response.json().await?;
+        Ok(embed_response.embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn chat_streams_ndjson() {
+        let server = MockServer::start(vec![(
+            200,
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Hi \"},\"done\":false}\n\
+             {\"message\":{\"role\":\"assistant\",\"content\":\"there\"},\"done\":false}\n\
+             {\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"prompt_eval_count\":12,\"eval_count\":3}\n",
+        )]);
+        let client = OllamaClient::new(
+            server.url.clone(),
+            "llama".to_string(),
+            GenerationConfig::default(),
+        );
+
+        let mut streamed = String::new();
+        let completion = client
+            .chat(&[ChatMessage::user("Hi")], &mut |text: &str| {
+                streamed.push_str(text)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(streamed, "Hi there");
+        assert_eq!(completion.text, "Hi there");
+        let usage = completion.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (12, 3));
+        assert_eq!(server.requests()[0].path, "/api/chat");
     }
 }
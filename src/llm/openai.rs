@@ -1,21 +1,30 @@
-use super::{LlmError, LlmProvider};
+use super::{read_lines, ChatMessage, Completion, LlmError, LlmProvider, OnText, Usage};
+use crate::config::GenerationConfig;
 use serde::{Deserialize, Serialize};
 
 const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
 
 pub struct OpenAIClient {
     api_key: String,
     model: String,
     base_url: String,
+    params: GenerationConfig,
     client: reqwest::Client,
 }
 
 impl OpenAIClient {
-    pub fn new(api_key: String, model: String, base_url: Option<String>) -> Self {
+    pub fn new(
+        api_key: String,
+        model: String,
+        base_url: Option<String>,
+        params: GenerationConfig,
+    ) -> Self {
         Self {
             api_key,
             model,
             base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            params,
             client: reqwest::Client::new(),
         }
     }
@@ -26,6 +35,22 @@ struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    /// Without it, streamed responses carry no token usage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+#[derive(Serialize)]
+struct StreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Serialize)]
@@ -37,6 +62,13 @@ struct Message {
 #[derive(Deserialize)]
 struct ChatResponse {
     choices: Vec<Choice>,
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Deserialize)]
+struct ChatUsage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
 }
 
 #[derive(Deserialize)]
@@ -49,24 +81,70 @@ struct ResponseMessage {
     content: String,
 }
 
-#[async_trait::async_trait]
-impl LlmProvider for OpenAIClient {
-    async fn generate(&self, prompt: &str) -> Result<String, LlmError> {
-        let request = ChatRequest {
+/// One server-sent event of a streamed response
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: Delta,
+}
+
+#[derive(Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl OpenAIClient {
+    fn request(&self, messages: &[ChatMessage], stream: bool) -> ChatRequest {
+        ChatRequest {
             model: self.model.clone(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-            temperature: 0.7,
-        };
+            messages: messages
+                .iter()
+                .map(|m| Message {
+                    role: m.role.to_string(),
+                    content: m.content.clone(),
+                })
+                .collect(),
+            temperature: self.params.temperature.unwrap_or(0.7),
+            top_p: self.params.top_p,
+            max_tokens: self.params.max_tokens,
+            seed: self.params.seed,
+            stream,
+            stream_options: stream.then_some(StreamOptions {
+                include_usage: true,
+            }),
+        }
+    }
 
+    async fn send(&self, request: &ChatRequest) -> Result<reqwest::Response, LlmError> {
         let response = self
             .client
             .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(request)
             .send()
             .await?;
 
@@ -74,6 +152,15 @@ impl LlmProvider for OpenAIClient {
             let error_text = response.text().await.unwrap_or_default();
             return Err(LlmError::ApiError(error_text));
         }
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for OpenAIClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion, LlmError> {
+        let request = self.request(&[ChatMessage::user(prompt)], false);
+        let response = self.send(&request).await?;
 
         let chat_response: ChatResponse = response.json().await?;
         let content = chat_response
@@ -82,6 +169,120 @@ impl LlmProvider for OpenAIClient {
             .map(|c| c.message.content.clone())
             .unwrap_or_default();
 
-        Ok(content)
+        Ok(Completion {
+            text: content,
+            usage: chat_response.usage.map(|u| Usage {
+                input_tokens: u.prompt_tokens,
+                output_tokens: u.completion_tokens,
+            }),
+        })
+    }
+
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        on_text: OnText<'_>,
+    ) -> Result<Completion, LlmError> {
+        let response = self.send(&self.request(messages, true)).await?;
+
+        let mut text = String::new();
+        let mut usage = None;
+        read_lines(response, |line| {
+            let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                return Ok(());
+            };
+            if data == "[DONE]" {
+                return Ok(());
+            }
+            let chunk: StreamChunk = serde_json::from_str(data)
+                .map_err(|e| LlmError::ApiError(format!("invalid stream event: {}", e)))?;
+            for content in chunk.choices.into_iter().filter_map(|c| c.delta.content) {
+                on_text(&content);
+                text.push_str(&content);
+            }
+            if let Some(u) = chunk.usage {
+                usage = Some(Usage {
+                    input_tokens: u.prompt_tokens,
+                    output_tokens: u.completion_tokens,
+                });
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(Completion { text, usage })
+    }
+
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        Some(DEFAULT_EMBEDDING_MODEL)
+    }
+
+    async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        let response = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&EmbeddingRequest {
+                model,
+                input: texts,
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::ApiError(error_text));
+        }
+
+        let mut embeddings: EmbeddingResponse = response.json().await?;
+        embeddings.data.sort_by_key(|d| d.index);
+        Ok(embeddings.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+
+    #[tokio::test]
+    async fn chat_streams_deltas() {
+        let server = MockServer::start(vec![(
+            200,
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n\
+             data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n\
+             data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\n\
+             data: {\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":2}}\n\n\
+             data: [DONE]\n\n",
+        )]);
+        let client = OpenAIClient::new(
+            "key".to_string(),
+            "gpt".to_string(),
+            Some(server.url.clone()),
+            GenerationConfig::default(),
+        );
+
+        let mut streamed = Vec::new();
+        let completion = client
+            .chat(
+                &[
+                    ChatMessage::user("Hi"),
+                    ChatMessage::assistant("Hey"),
+                    ChatMessage::user("Again"),
+                ],
+                &mut |text: &str| streamed.push(text.to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(streamed, vec!["Hel", "lo"]);
+        assert_eq!(completion.text, "Hello");
+        let usage = completion.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (12, 2));
+        let request = server.requests()[0].json();
+        assert_eq!(request["stream"], true);
+        assert_eq!(request["stream_options"]["include_usage"], true);
+        assert_eq!(request["messages"][1]["role"], "assistant");
+        assert_eq!(request["messages"][2]["content"], "Again");
     }
 }
@@ -1,10 +1,25 @@
+mod changelog;
+mod codeowners;
 mod commands;
 mod config;
+mod conflict;
+mod forge;
 mod git;
+mod issues;
 mod llm;
+#[cfg(test)]
+mod mock;
+mod output;
+mod patch;
+mod risk;
+mod search;
+mod version;
 
 use clap::{Parser, Subcommand};
-use commands::{commit, config as config_cmd, pr};
+use commands::{
+    branch, changelog as changelog_cmd, commit, config as config_cmd, explain, hook, log, pr,
+    release, resolve, review, split, squash, stash, summary, tag,
+};
 
 #[derive(Parser)]
 #[command(name = "git-ai")]
@@ -25,6 +40,45 @@ enum Commands {
 
     /// Generate PR title and description
     Pr(pr::PrArgs),
+
+    /// Manage the prepare-commit-msg hook
+    Hook(hook::HookArgs),
+
+    /// Review the branch or staged changes
+    Review(review::ReviewArgs),
+
+    /// Generate changelog entries from commits
+    Changelog(changelog_cmd::ChangelogArgs),
+
+    /// Suggest the next version and write release notes
+    Release(release::ReleaseArgs),
+
+    /// Explain what a commit or range of commits does
+    Explain(explain::ExplainArgs),
+
+    /// Search the history with a question
+    Log(log::LogArgs),
+
+    /// Summarize recent commits for a standup or report
+    Summary(summary::SummaryArgs),
+
+    /// Suggest a branch name and switch to it
+    Branch(branch::BranchArgs),
+
+    /// Split the staged changes into several commits
+    Split(split::SplitArgs),
+
+    /// Squash the branch into one commit with a generated message
+    Squash(squash::SquashArgs),
+
+    /// Resolve merge conflicts with proposed resolutions
+    Resolve(resolve::ResolveArgs),
+
+    /// Create an annotated tag with a message describing the changes since the previous tag
+    Tag(tag::TagArgs),
+
+    /// Stash local changes with a generated message
+    Stash(stash::StashArgs),
 }
 
 #[tokio::main]
@@ -35,6 +89,19 @@ async fn main() -> anyhow::Result<()> {
         Commands::Config(args) => config_cmd::run(args).await?,
         Commands::Commit(args) => commit::run(args).await?,
         Commands::Pr(args) => pr::run(args).await?,
+        Commands::Hook(args) => hook::run(args).await?,
+        Commands::Review(args) => review::run(args).await?,
+        Commands::Changelog(args) => changelog_cmd::run(args).await?,
+        Commands::Release(args) => release::run(args).await?,
+        Commands::Explain(args) => explain::run(args).await?,
+        Commands::Log(args) => log::run(args).await?,
+        Commands::Summary(args) => summary::run(args).await?,
+        Commands::Branch(args) => branch::run(args).await?,
+        Commands::Split(args) => split::run(args).await?,
+        Commands::Squash(args) => squash::run(args).await?,
+        Commands::Resolve(args) => resolve::run(args).await?,
+        Commands::Tag(args) => tag::run(args).await?,
+        Commands::Stash(args) => stash::run(args).await?,
     }
 
     Ok(())
@@ -0,0 +1,32 @@
+use clap::ValueEnum;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, colored output
+    #[default]
+    Text,
+    /// Machine-readable JSON on stdout
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == Self::Json
+    }
+
+    /// Print a progress message. In JSON mode it goes to stderr so that
+    /// stdout only carries the JSON document.
+    pub fn status(self, message: impl Display) {
+        match self {
+            Self::Text => println!("{}", message),
+            Self::Json => eprintln!("{}", message),
+        }
+    }
+}
+
+/// Print a value as pretty JSON on stdout
+pub fn print_json<T: serde::Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Patches split into files and hunks that can be applied on their own
+
+/// The changes to one file in a patch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    pub path: String,
+    /// Lines before the first hunk (`diff --git`, `index`, `---`, `+++`, ...)
+    header: String,
+    pub hunks: Vec<Hunk>,
+}
+
+/// One `@@` hunk, including its header line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub text: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl Hunk {
+    /// The `@@ -a,b +c,d @@ context` line
+    pub fn header(&self) -> &str {
+        self.text.lines().next().unwrap_or_default()
+    }
+}
+
+impl FilePatch {
+    /// Whether the hunks can be applied separately. Creations, deletions,
+    /// mode changes and binary files have to be applied as a whole.
+    pub fn is_splittable(&self) -> bool {
+        !self.hunks.is_empty()
+            && self.header.lines().all(|line| {
+                line.starts_with("diff --git ")
+                    || line.starts_with("index ")
+                    || line.starts_with("--- a/")
+                    || line.starts_with("+++ b/")
+            })
+    }
+
+    /// A patch with the file header and the selected hunks
+    pub fn patch(&self, hunks: &[usize]) -> String {
+        let mut patch = self.header.clone();
+        for &i in hunks {
+            patch.push_str(&self.hunks[i].text);
+        }
+        patch
+    }
+}
+
+/// Split a `git diff` patch into files and hunks
+pub fn parse(diff: &str) -> Vec<FilePatch> {
+    let mut files: Vec<FilePatch> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            files.push(FilePatch {
+                path: path_from_diff_line(line),
+                header: line.to_string(),
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            file.hunks.push(Hunk {
+                text: line.to_string(),
+                additions: 0,
+                deletions: 0,
+            });
+        } else if let Some(hunk) = file.hunks.last_mut() {
+            hunk.text.push_str(line);
+            if line.starts_with('+') {
+                hunk.additions += 1;
+            } else if line.starts_with('-') {
+                hunk.deletions += 1;
+            }
+        } else {
+            if let Some(path) = line
+                .strip_prefix("+++ b/")
+                .or_else(|| line.strip_prefix("--- a/"))
+            {
+                file.path = path.trim_end_matches(['\n', '\r']).to_string();
+            }
+            file.header.push_str(line);
+        }
+    }
+    files
+}
+
+/// The new path from a `diff --git a/<path> b/<path>` line
+fn path_from_diff_line(line: &str) -> String {
+    let line = line.trim_end_matches(['\n', '\r']);
+    match line.rfind(" b/") {
+        Some(i) => line[i + 3..].to_string(),
+        None => line.trim_start_matches("diff --git ").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ a
+-b
++B
+ c
+@@ -20,2 +20,3 @@ fn main() {
+ x
++y
+ z
+diff --git a/docs/new.md b/docs/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/docs/new.md
+@@ -0,0 +1 @@
++hello
+";
+
+    #[test]
+    fn parse_files_and_hunks() {
+        let files = parse(DIFF);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].hunks.len(), 2);
+        assert_eq!(files[0].hunks[1].header(), "@@ -20,2 +20,3 @@ fn main() {");
+        assert_eq!(files[0].hunks[0].deletions, 1);
+        assert_eq!(files[0].hunks[1].additions, 1);
+        assert!(files[0].is_splittable());
+
+        assert_eq!(files[1].path, "docs/new.md");
+        assert!(!files[1].is_splittable());
+    }
+
+    #[test]
+    fn patch_with_selected_hunks() {
+        let files = parse(DIFF);
+        let patch = files[0].patch(&[1]);
+        assert!(patch.starts_with("diff --git a/src/lib.rs b/src/lib.rs\n"));
+        assert!(patch.contains("+++ b/src/lib.rs\n@@ -20,2 +20,3 @@"));
+        assert!(!patch.contains("+B"));
+
+        let all = files[0].patch(&[0, 1]) + &files[1].patch(&[0]);
+        assert_eq!(all, DIFF);
+    }
+}
@@ -0,0 +1,350 @@
+//! Facts about a change that help reviewers judge its risk
+
+use crate::codeowners::pattern_to_regex;
+use crate::config::RiskConfig;
+use crate::git::FileStat;
+use serde::Serialize;
+
+/// Size class by number of changed lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SizeClass {
+    XS,
+    S,
+    M,
+    L,
+    XL,
+}
+
+impl SizeClass {
+    pub fn from_lines(lines: usize) -> Self {
+        match lines {
+            0..=9 => Self::XS,
+            10..=99 => Self::S,
+            100..=499 => Self::M,
+            500..=999 => Self::L,
+            _ => Self::XL,
+        }
+    }
+}
+
+impl std::fmt::Display for SizeClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::XS => write!(f, "XS"),
+            Self::S => write!(f, "S"),
+            Self::M => write!(f, "M"),
+            Self::L => write!(f, "L"),
+            Self::XL => write!(f, "XL"),
+        }
+    }
+}
+
+/// Parts of a codebase where changes deserve extra care
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Area {
+    Migrations,
+    Ci,
+    Auth,
+    Dependencies,
+}
+
+impl std::fmt::Display for Area {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Migrations => write!(f, "database migrations"),
+            Self::Ci => write!(f, "CI config"),
+            Self::Auth => write!(f, "auth code"),
+            Self::Dependencies => write!(f, "dependency manifests"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SensitiveArea {
+    pub area: Area,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RiskReport {
+    pub size: SizeClass,
+    pub files: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    pub sensitive: Vec<SensitiveArea>,
+    /// Source files changed without any test changes
+    pub missing_tests: bool,
+    /// Suggested labels from the size class and the configured path rules
+    pub labels: Vec<String>,
+}
+
+impl RiskReport {
+    pub fn assess(stats: &[FileStat], config: &RiskConfig) -> Self {
+        let additions = stats.iter().map(|s| s.additions).sum();
+        let deletions = stats.iter().map(|s| s.deletions).sum();
+        let size = SizeClass::from_lines(additions + deletions);
+
+        let mut sensitive: Vec<SensitiveArea> = Vec::new();
+        for stat in stats {
+            let Some(area) = classify(&stat.path) else {
+                continue;
+            };
+            match sensitive.iter_mut().find(|s| s.area == area) {
+                Some(found) => found.paths.push(stat.path.clone()),
+                None => sensitive.push(SensitiveArea {
+                    area,
+                    paths: vec![stat.path.clone()],
+                }),
+            }
+        }
+
+        let changes_source = stats.iter().any(|s| is_source_file(&s.path));
+        let changes_tests = stats.iter().any(|s| is_test_file(&s.path));
+
+        let mut labels = Vec::new();
+        if !config.size_label_prefix.is_empty() {
+            labels.push(format!("{}{}", config.size_label_prefix, size));
+        }
+        for rule in &config.labels {
+            let Some(pattern) = pattern_to_regex(&rule.pattern) else {
+                continue;
+            };
+            if !labels.contains(&rule.label) && stats.iter().any(|s| pattern.is_match(&s.path)) {
+                labels.push(rule.label.clone());
+            }
+        }
+
+        Self {
+            size,
+            files: stats.len(),
+            additions,
+            deletions,
+            sensitive,
+            missing_tests: changes_source && !changes_tests,
+            labels,
+        }
+    }
+
+    /// The facts as a bullet list for the prompt
+    pub fn facts(&self) -> String {
+        let mut facts = vec![format!(
+            "- Size: {} ({}, +{} -{} lines)",
+            self.size,
+            files_label(self.files),
+            self.additions,
+            self.deletions
+        )];
+        for area in &self.sensitive {
+            facts.push(format!(
+                "- Touches {}: {}",
+                area.area,
+                area.paths.join(", ")
+            ));
+        }
+        if self.missing_tests {
+            facts.push("- Source files changed but no tests were added or updated".to_string());
+        }
+        facts.join("\n")
+    }
+
+    /// One-line summary appended to the PR body
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!(
+            "**Size:** {} ({}, +{} −{})",
+            self.size,
+            files_label(self.files),
+            self.additions,
+            self.deletions
+        )];
+        if !self.sensitive.is_empty() {
+            let areas: Vec<String> = self.sensitive.iter().map(|s| s.area.to_string()).collect();
+            parts.push(format!("**Sensitive:** {}", areas.join(", ")));
+        }
+        if self.missing_tests {
+            parts.push("**Tests:** none changed".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+fn files_label(count: usize) -> String {
+    if count == 1 {
+        "1 file".to_string()
+    } else {
+        format!("{} files", count)
+    }
+}
+
+const DEPENDENCY_MANIFESTS: [&str; 16] = [
+    "cargo.toml",
+    "cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "go.mod",
+    "go.sum",
+    "requirements.txt",
+    "pyproject.toml",
+    "poetry.lock",
+    "gemfile",
+    "gemfile.lock",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+];
+
+const CI_FILES: [&str; 5] = [
+    ".gitlab-ci.yml",
+    ".travis.yml",
+    "jenkinsfile",
+    "azure-pipelines.yml",
+    "bitbucket-pipelines.yml",
+];
+
+const CI_DIRS: [&str; 4] = [
+    ".github/workflows/",
+    ".circleci/",
+    ".gitea/workflows/",
+    ".forgejo/workflows/",
+];
+
+const AUTH_WORDS: [&str; 11] = [
+    "login",
+    "logout",
+    "oauth",
+    "session",
+    "sessions",
+    "password",
+    "passwords",
+    "credentials",
+    "permissions",
+    "security",
+    "crypto",
+];
+
+fn classify(path: &str) -> Option<Area> {
+    let lower = path.to_ascii_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+
+    if lower
+        .split('/')
+        .any(|dir| dir == "migrations" || dir == "migrate")
+    {
+        return Some(Area::Migrations);
+    }
+    if CI_FILES.contains(&file_name) || CI_DIRS.iter().any(|dir| lower.starts_with(dir)) {
+        return Some(Area::Ci);
+    }
+    if DEPENDENCY_MANIFESTS.contains(&file_name) {
+        return Some(Area::Dependencies);
+    }
+    let is_auth_word = |word: &str| {
+        AUTH_WORDS.contains(&word)
+            || (word.starts_with("auth") && !word.starts_with("author"))
+            || word.starts_with("authoriz")
+    };
+    if lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(is_auth_word)
+    {
+        return Some(Area::Auth);
+    }
+    None
+}
+
+const SOURCE_EXTENSIONS: [&str; 16] = [
+    "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "rb", "c", "cc", "cpp", "cs",
+    "swift", "php",
+];
+
+fn is_source_file(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+fn is_test_file(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    lower
+        .split('/')
+        .any(|dir| matches!(dir, "test" | "tests" | "spec" | "__tests__"))
+        || file_name.starts_with("test_")
+        || ["_test.", ".test.", ".spec.", "_spec.", "tests."]
+            .iter()
+            .any(|marker| file_name.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LabelRule;
+
+    fn stat(path: &str, additions: usize, deletions: usize) -> FileStat {
+        FileStat {
+            path: path.to_string(),
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn size_class_boundaries() {
+        assert_eq!(SizeClass::from_lines(0), SizeClass::XS);
+        assert_eq!(SizeClass::from_lines(10), SizeClass::S);
+        assert_eq!(SizeClass::from_lines(499), SizeClass::M);
+        assert_eq!(SizeClass::from_lines(999), SizeClass::L);
+        assert_eq!(SizeClass::from_lines(1000), SizeClass::XL);
+    }
+
+    #[test]
+    fn classify_sensitive_paths() {
+        assert_eq!(
+            classify("db/migrations/0001_init.sql"),
+            Some(Area::Migrations)
+        );
+        assert_eq!(classify(".github/workflows/ci.yml"), Some(Area::Ci));
+        assert_eq!(classify("web/package.json"), Some(Area::Dependencies));
+        assert_eq!(classify("src/auth/token.rs"), Some(Area::Auth));
+        assert_eq!(classify("src/AuthService.ts"), Some(Area::Auth));
+        assert_eq!(classify("src/user_session.py"), Some(Area::Auth));
+        assert_eq!(classify("AUTHORS.md"), None);
+        assert_eq!(classify("src/main.rs"), None);
+    }
+
+    #[test]
+    fn assess_reports_missing_tests_and_labels() {
+        let config = RiskConfig {
+            size_label_prefix: "size/".to_string(),
+            labels: vec![
+                LabelRule {
+                    pattern: "docs/".to_string(),
+                    label: "documentation".to_string(),
+                },
+                LabelRule {
+                    pattern: "*.rs".to_string(),
+                    label: "rust".to_string(),
+                },
+            ],
+        };
+        let report = RiskReport::assess(
+            &[stat("src/lib.rs", 120, 30), stat("Cargo.toml", 1, 0)],
+            &config,
+        );
+        assert_eq!(report.size, SizeClass::M);
+        assert!(report.missing_tests);
+        assert_eq!(report.sensitive[0].area, Area::Dependencies);
+        assert_eq!(report.labels, vec!["size/M", "rust"]);
+        assert_eq!(
+            report.summary(),
+            "**Size:** M (2 files, +121 −30) · **Sensitive:** dependency manifests · **Tests:** none changed"
+        );
+
+        let report = RiskReport::assess(
+            &[stat("src/lib.rs", 5, 0), stat("tests/lib_test.rs", 5, 0)],
+            &config,
+        );
+        assert!(!report.missing_tests);
+    }
+}
@@ -0,0 +1,479 @@
+//! Local index of the commit history, searched by `git ai log`
+
+use crate::git::{Commit, Git, GitError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Bumped when the stored fields change, to rebuild old indexes
+const INDEX_VERSION: u32 = 1;
+
+/// Identifiers kept from the changed lines of each commit
+const MAX_TERMS: usize = 40;
+
+/// Commits read from `git log` at a time while indexing
+const BATCH_SIZE: usize = 200;
+
+/// Bytes of each commit's diff that are indexed; the rest of very large
+/// commits (vendored code, generated files) is left out
+const MAX_PATCH_BYTES: usize = 256 * 1024;
+
+/// BM25 parameters
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "did", "do", "does", "for", "from", "has",
+    "have", "how", "in", "is", "it", "of", "on", "or", "that", "the", "this", "to", "was", "we",
+    "were", "what", "when", "where", "which", "who", "why", "will", "with",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Index {
+    version: u32,
+    /// Commits reachable from these were indexed by earlier updates
+    #[serde(default)]
+    tips: Vec<String>,
+    /// Model that produced the stored embeddings
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+    #[serde(default)]
+    pub commits: Vec<IndexedCommit>,
+}
+
+/// A commit with a summary of its diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedCommit {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+    pub body: String,
+    pub files: Vec<String>,
+    /// Function or section names from the hunk headers
+    pub contexts: Vec<String>,
+    /// Identifiers from the changed lines
+    pub terms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// A search result
+#[derive(Debug)]
+pub struct Match<'a> {
+    pub commit: &'a IndexedCommit,
+    pub score: f32,
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            tips: Vec::new(),
+            embedding_model: None,
+            commits: Vec::new(),
+        }
+    }
+}
+
+impl Index {
+    /// Location of the index, shared by all worktrees of the repository
+    pub fn path() -> Result<PathBuf, GitError> {
+        Ok(Git::common_dir()?.join("git-ai").join("log-index.json"))
+    }
+
+    /// Load the index, or start a new one if it's missing or outdated
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Index the commits reachable from HEAD that aren't indexed yet.
+    /// Returns the number of commits added.
+    pub fn update(&mut self) -> Result<usize, GitError> {
+        let head = Git::rev_parse("HEAD")?;
+        if self.tips.contains(&head) {
+            return Ok(0);
+        }
+
+        // Tips rewritten by an amend or rebase are unreachable, and gone
+        // once garbage collected; git log fails on missing ones
+        self.tips
+            .retain(|tip| Git::has_commit(tip) && Git::is_referenced(tip));
+
+        let mut revs = vec![head.clone()];
+        revs.extend(self.tips.iter().map(|tip| format!("^{}", tip)));
+        let revs: Vec<&str> = revs.iter().map(String::as_str).collect();
+        // Without the dropped tips, commits below them are walked again
+        let indexed: HashSet<&str> = self.commits.iter().map(|c| c.hash.as_str()).collect();
+        let mut new: Vec<IndexedCommit> = Vec::new();
+        let mut skip = 0;
+        loop {
+            let batch = Git::get_commit_patches(&revs, skip, BATCH_SIZE, MAX_PATCH_BYTES)?;
+            skip += batch.len();
+            let last = batch.len() < BATCH_SIZE;
+            new.extend(
+                batch
+                    .into_iter()
+                    .filter(|(commit, _)| !indexed.contains(commit.hash.as_str()))
+                    .map(|(commit, patch)| IndexedCommit::new(commit, &patch)),
+            );
+            if last {
+                break;
+            }
+        }
+        let added = new.len();
+        self.commits.splice(0..0, new);
+
+        // Tips that HEAD contains are covered by HEAD from now on
+        self.tips.retain(|tip| !Git::is_ancestor(tip, &head));
+        self.tips.push(head);
+        Ok(added)
+    }
+
+    /// Rank the commits in `scope` against the query: BM25 over the commit
+    /// text, blended with embedding similarity when a query embedding is given
+    pub fn search(
+        &self,
+        query: &str,
+        query_embedding: Option<&[f32]>,
+        scope: &HashSet<String>,
+        limit: usize,
+    ) -> Vec<Match<'_>> {
+        let commits: Vec<&IndexedCommit> = self
+            .commits
+            .iter()
+            .filter(|commit| scope.contains(&commit.hash))
+            .collect();
+        let query_tokens: HashSet<String> = tokenize(query).into_iter().collect();
+        let documents: Vec<Vec<String>> = commits.iter().map(|c| c.tokens()).collect();
+        let lexical = bm25(&query_tokens, &documents);
+        let max_lexical = lexical.iter().copied().fold(0.0, f32::max);
+
+        let mut matches: Vec<Match> = commits
+            .into_iter()
+            .zip(lexical)
+            .filter_map(|(commit, lexical)| {
+                let lexical = if max_lexical > 0.0 {
+                    lexical / max_lexical
+                } else {
+                    0.0
+                };
+                let semantic = query_embedding
+                    .zip(commit.embedding.as_deref())
+                    .map(|(query, commit)| cosine(query, commit));
+                let score = match semantic {
+                    Some(semantic) => 0.5 * lexical + 0.5 * semantic,
+                    None if lexical > 0.0 => lexical,
+                    None => return None,
+                };
+                Some(Match { commit, score })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        matches
+    }
+}
+
+impl IndexedCommit {
+    pub fn new(commit: Commit, patch: &str) -> Self {
+        let mut files = Vec::new();
+        let mut contexts = Vec::new();
+        let mut terms = Vec::new();
+        for line in patch.lines() {
+            if let Some(paths) = line.strip_prefix("diff --git ") {
+                if let Some((_, path)) = paths.rsplit_once(" b/") {
+                    files.push(path.to_string());
+                }
+            } else if line.starts_with("+++ ") || line.starts_with("--- ") {
+                continue;
+            } else if let Some(header) = line.strip_prefix("@@ ") {
+                if let Some((_, context)) = header.split_once("@@") {
+                    let context = context.trim();
+                    if !context.is_empty() && !contexts.iter().any(|c| c == context) {
+                        contexts.push(context.to_string());
+                    }
+                }
+            } else if line.starts_with('+') || line.starts_with('-') {
+                for word in line[1..].split(|c: char| !c.is_alphanumeric() && c != '_') {
+                    if terms.len() < MAX_TERMS
+                        && word.len() >= 4
+                        && word.starts_with(|c: char| c.is_alphabetic())
+                        && !terms.iter().any(|t| t == word)
+                    {
+                        terms.push(word.to_string());
+                    }
+                }
+            }
+        }
+
+        Self {
+            hash: commit.hash,
+            author: commit.author,
+            date: commit.date,
+            subject: commit.subject,
+            body: commit.body,
+            files,
+            contexts,
+            terms,
+            embedding: None,
+        }
+    }
+
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    /// Text embedded for semantic search
+    pub fn embedding_text(&self) -> String {
+        let mut text = format!(
+            "{}\n{}\nFiles: {}\n{}",
+            self.subject,
+            self.body,
+            self.files.join(", "),
+            self.contexts.join("\n")
+        );
+        if text.len() > 2000 {
+            let end = (0..=2000)
+                .rev()
+                .find(|&i| text.is_char_boundary(i))
+                .unwrap_or(0);
+            text.truncate(end);
+        }
+        text
+    }
+
+    /// Search tokens; the subject counts twice
+    fn tokens(&self) -> Vec<String> {
+        let mut tokens = tokenize(&self.subject);
+        tokens.extend(tokenize(&self.subject));
+        tokens.extend(tokenize(&self.body));
+        for text in self.files.iter().chain(&self.contexts).chain(&self.terms) {
+            tokens.extend(tokenize(text));
+        }
+        tokens
+    }
+}
+
+/// Split text into lowercase, lightly stemmed words. Identifiers are split
+/// at underscores and camelCase boundaries.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut start = 0;
+        let chars: Vec<(usize, char)> = word.char_indices().collect();
+        for window in chars.windows(2) {
+            let ((_, previous), (i, current)) = (window[0], window[1]);
+            if previous.is_lowercase() && current.is_uppercase() {
+                tokens.push(&word[start..i]);
+                start = i;
+            }
+        }
+        tokens.push(&word[start..]);
+    }
+
+    tokens
+        .into_iter()
+        .map(str::to_lowercase)
+        .filter(|token| token.chars().count() >= 2 && !STOPWORDS.contains(&token.as_str()))
+        .map(|token| stem(&token))
+        .collect()
+}
+
+/// Strip common English suffixes so "retries" and "retry" match
+fn stem(word: &str) -> String {
+    for (suffix, replacement) in [
+        ("ies", "y"),
+        ("ing", ""),
+        ("ed", ""),
+        ("es", ""),
+        ("s", ""),
+        ("e", ""),
+    ] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.chars().count() >= 3 {
+                return format!("{}{}", stem, replacement);
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// BM25 score of each document for the query
+fn bm25(query: &HashSet<String>, documents: &[Vec<String>]) -> Vec<f32> {
+    if documents.is_empty() {
+        return Vec::new();
+    }
+    let count = documents.len() as f32;
+    let average_len = documents.iter().map(Vec::len).sum::<usize>() as f32 / count;
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for document in documents {
+        let unique: HashSet<&str> = document.iter().map(String::as_str).collect();
+        for token in unique.into_iter().filter(|t| query.contains(*t)) {
+            *document_frequency.entry(token).or_default() += 1;
+        }
+    }
+
+    documents
+        .iter()
+        .map(|document| {
+            let len = document.len() as f32;
+            query
+                .iter()
+                .map(|term| {
+                    let frequency = document.iter().filter(|t| *t == term).count() as f32;
+                    if frequency == 0.0 {
+                        return 0.0;
+                    }
+                    let df = document_frequency.get(term.as_str()).copied().unwrap_or(0) as f32;
+                    let idf = ((count - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    idf * frequency * (K1 + 1.0)
+                        / (frequency + K1 * (1.0 - B + B * len / average_len.max(1.0)))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexed(hash: &str, subject: &str, patch: &str) -> IndexedCommit {
+        IndexedCommit::new(Commit::test(subject).with_hash(hash), patch)
+    }
+
+    #[test]
+    fn tokenize_splits_identifiers_and_stems() {
+        assert_eq!(
+            tokenize("When did we change the retryPolicy for max_retries?"),
+            vec!["chang", "retry", "policy", "max", "retry"]
+        );
+    }
+
+    #[test]
+    fn indexed_commit_summarizes_patch() {
+        let patch = "\ndiff --git a/src/http.rs b/src/http.rs\n--- a/src/http.rs\n+++ b/src/http.rs\n@@ -10,1 +10,1 @@ fn send_request()\n-    let attempts = 3;\n+    let attempts = config.max_retries;\n";
+        let commit = indexed("a".repeat(40).as_str(), "fix: tune client", patch);
+        assert_eq!(commit.files, vec!["src/http.rs"]);
+        assert_eq!(commit.contexts, vec!["fn send_request()"]);
+        assert_eq!(commit.terms, vec!["attempts", "config", "max_retries"]);
+    }
+
+    #[test]
+    fn search_ranks_matching_commits() {
+        let index = Index {
+            commits: vec![
+                indexed("1", "docs: update README", ""),
+                indexed(
+                    "2",
+                    "fix: back off between attempts",
+                    "@@ -1 +1 @@ fn retry_request()\n+sleep(backoff)\n",
+                ),
+                indexed("3", "feat: add retry limit", ""),
+            ],
+            ..Index::default()
+        };
+        let scope: HashSet<String> = ["1", "2", "3"].iter().map(|s| s.to_string()).collect();
+
+        let matches = index.search("when did we change the retry logic?", None, &scope, 5);
+        let hashes: Vec<&str> = matches.iter().map(|m| m.commit.hash.as_str()).collect();
+        assert_eq!(hashes.len(), 2);
+        assert!(hashes.contains(&"2") && hashes.contains(&"3"));
+
+        let scope: HashSet<String> = ["2".to_string()].into_iter().collect();
+        assert_eq!(index.search("retry", None, &scope, 5).len(), 1);
+    }
+
+    #[test]
+    fn update_after_rewriting_head() {
+        let repo = std::env::temp_dir().join(format!("git-ai-index-{}", std::process::id()));
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+        };
+        git(&["init", "--quiet"]);
+        git(&[
+            "commit",
+            "--quiet",
+            "--allow-empty",
+            "-m",
+            "feat: add retry",
+        ]);
+        git(&[
+            "commit",
+            "--quiet",
+            "--allow-empty",
+            "-m",
+            "fix: retry delay",
+        ]);
+
+        // The only test that changes directories; Index works on the current repository
+        std::env::set_current_dir(&repo).unwrap();
+        let mut index = Index::default();
+        assert_eq!(index.update().unwrap(), 2);
+
+        git(&[
+            "commit",
+            "--quiet",
+            "--amend",
+            "--allow-empty",
+            "-m",
+            "fix: retry backoff",
+        ]);
+        git(&["reflog", "expire", "--expire=now", "--all"]);
+        git(&["gc", "--quiet", "--prune=now"]);
+        let result = index.update();
+        std::fs::remove_dir_all(&repo).unwrap();
+
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(index.tips.len(), 1);
+        let subjects: Vec<&str> = index.commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(
+            subjects,
+            ["fix: retry backoff", "fix: retry delay", "feat: add retry"]
+        );
+    }
+
+    #[test]
+    fn cosine_similarity() {
+        assert!((cosine(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+}
@@ -0,0 +1,233 @@
+//! Semantic versions and the bump implied by conventional commits
+
+use crate::changelog::Conventional;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// A `MAJOR.MINOR.PATCH` version, as found in tags like `v1.2.3`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release suffix, e.g. `rc.1`
+    pub pre: Option<String>,
+}
+
+impl Version {
+    /// Parse a version or tag name, with or without a `v` prefix. Build
+    /// metadata (`+build.5`) doesn't make a different version and is dropped.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let text = text.split_once('+').map_or(text, |(version, _)| version);
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (text, None),
+        };
+        let mut parts = core.split('.');
+        let version = Self {
+            major: parts.next()?.parse().ok()?,
+            minor: parts.next()?.parse().ok()?,
+            patch: parts.next()?.parse().ok()?,
+            pre,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    pub fn bump(&self, bump: Bump) -> Self {
+        let (major, minor, patch) = match bump {
+            // A pre-release of the bumped version is released as is:
+            // 1.3.0-rc.1 becomes 1.3.0 for a minor or patch bump
+            _ if self.pre.is_some() && self.patch == 0 && self.minor == 0 => {
+                (self.major, self.minor, self.patch)
+            }
+            Bump::Minor | Bump::Patch if self.pre.is_some() && self.patch == 0 => {
+                (self.major, self.minor, self.patch)
+            }
+            Bump::Patch if self.pre.is_some() => (self.major, self.minor, self.patch),
+            Bump::Major => (self.major + 1, 0, 0),
+            Bump::Minor => (self.major, self.minor + 1, 0),
+            Bump::Patch => (self.major, self.minor, self.patch + 1),
+        };
+        Self {
+            major,
+            minor,
+            patch,
+            pre: None,
+        }
+    }
+}
+
+impl Ord for Version {
+    /// Pre-releases come before the release they lead up to
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (Some(a), Some(b)) => compare_pre_releases(a, b),
+            })
+    }
+}
+
+/// Compare pre-releases identifier by identifier: numbers numerically and
+/// before other identifiers, so `rc.2 < rc.10 < rc.beta` (SemVer §11)
+fn compare_pre_releases(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(ref pre) = self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// Bump implied by a set of commits: breaking changes are major, features
+    /// minor, anything else a patch. Before 1.0.0, breaking changes only bump
+    /// the minor version.
+    pub fn from_commits(commits: &[Conventional], current: &Version) -> Self {
+        let bump = if commits.iter().any(|c| c.breaking) {
+            Self::Major
+        } else if commits.iter().any(|c| c.kind == "feat") {
+            Self::Minor
+        } else {
+            Self::Patch
+        };
+        if current.major == 0 {
+            bump.min(Self::Minor)
+        } else {
+            bump
+        }
+    }
+}
+
+impl std::fmt::Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Patch => write!(f, "patch"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conventional(kind: &str, breaking: bool) -> Conventional {
+        Conventional {
+            kind: kind.to_string(),
+            scope: None,
+            breaking,
+            description: "change".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_versions() {
+        let version = Version::parse("v1.2.3").unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (1, 2, 3));
+        assert_eq!(
+            Version::parse("2.0.0-rc.1").unwrap().pre.as_deref(),
+            Some("rc.1")
+        );
+        assert!(Version::parse("release-2024").is_none());
+        assert!(Version::parse("1.2").is_none());
+        assert!(Version::parse("1.2.3.4").is_none());
+        assert!(Version::parse("v1.2.3").unwrap() < Version::parse("v1.10.0").unwrap());
+        assert!(Version::parse("1.0.0-rc.1").unwrap() < Version::parse("1.0.0").unwrap());
+
+        let build = Version::parse("v1.2.3+build.5").unwrap();
+        assert_eq!(build.pre, None);
+        assert_eq!(build.bump(Bump::Patch).to_string(), "1.2.4");
+        assert_eq!(
+            Version::parse("1.2.3-rc.1+exp.sha.5114f85")
+                .unwrap()
+                .pre
+                .as_deref(),
+            Some("rc.1")
+        );
+
+        let version = |text| Version::parse(text).unwrap();
+        assert!(version("1.0.0-rc.2") < version("1.0.0-rc.10"));
+        assert!(version("1.0.0-alpha") < version("1.0.0-alpha.1"));
+        assert!(version("1.0.0-alpha.1") < version("1.0.0-alpha.beta"));
+        assert!(version("1.0.0-beta.11") < version("1.0.0-rc.1"));
+    }
+
+    #[test]
+    fn bump_versions() {
+        let version = Version::parse("1.2.3").unwrap();
+        assert_eq!(version.bump(Bump::Major).to_string(), "2.0.0");
+        assert_eq!(version.bump(Bump::Minor).to_string(), "1.3.0");
+        assert_eq!(version.bump(Bump::Patch).to_string(), "1.2.4");
+        let rc = Version::parse("1.3.0-rc.2").unwrap();
+        assert_eq!(rc.bump(Bump::Patch).to_string(), "1.3.0");
+        assert_eq!(rc.bump(Bump::Minor).to_string(), "1.3.0");
+        assert_eq!(rc.bump(Bump::Major).to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn bump_from_commits() {
+        let stable = Version::parse("1.0.0").unwrap();
+        let fix = conventional("fix", false);
+        let feat = conventional("feat", false);
+        let breaking = conventional("refactor", true);
+        assert_eq!(
+            Bump::from_commits(std::slice::from_ref(&fix), &stable),
+            Bump::Patch
+        );
+        assert_eq!(
+            Bump::from_commits(&[fix.clone(), feat.clone()], &stable),
+            Bump::Minor
+        );
+        assert_eq!(
+            Bump::from_commits(&[feat, breaking.clone()], &stable),
+            Bump::Major
+        );
+
+        let initial = Version::parse("0.3.1").unwrap();
+        assert_eq!(Bump::from_commits(&[breaking], &initial), Bump::Minor);
+    }
+}